@@ -0,0 +1,53 @@
+use std::borrow::Cow;
+
+use crate::InputRange;
+
+/// A non-fatal diagnostic emitted during parsing
+///
+/// See [`ParserInput::emit_diagnostic`](`crate::ParserInput::emit_diagnostic`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Severity of the diagnostic
+    pub severity: Severity,
+
+    /// Location of the diagnostic
+    pub at: InputRange,
+
+    /// Diagnostic's message
+    pub message: Cow<'static, str>,
+}
+
+impl Diagnostic {
+    /// Create a new diagnostic
+    pub fn new(severity: Severity, at: InputRange, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            severity,
+            at,
+            message: message.into(),
+        }
+    }
+
+    /// Create a warning
+    pub fn warning(at: InputRange, message: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(Severity::Warning, at, message)
+    }
+
+    /// Create a note
+    pub fn note(at: InputRange, message: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(Severity::Note, at, message)
+    }
+}
+
+/// Severity of a [`Diagnostic`]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Informative message
+    Note,
+
+    /// Something that is likely wrong, but does not prevent parsing
+    Warning,
+
+    /// Something that is wrong, but does not prevent parsing
+    Error,
+}
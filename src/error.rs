@@ -1,6 +1,10 @@
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, error::Error, fmt, sync::Arc};
 
-use crate::{InputLocation, InputRange, Span};
+use crate::{
+    ApplySuggestionsErr, ByteSource, DefaultFormatter, ErrorKind, InputKind, InputLocation,
+    InputRange, InputSource, LineIndex, Message, MessageFormatter, ParserInput, Span, Suggestion,
+    apply_suggestions,
+};
 
 /// Result of a parsing operation
 ///
@@ -9,16 +13,74 @@ use crate::{InputLocation, InputRange, Span};
 pub type ParserResult<T> = ::std::result::Result<Span<T>, ParsingError>;
 
 /// Result of a parsing error
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParsingError {
     /// Content of the error
     inner: ParsingErrorInner,
 
+    /// Whether the error is critical
+    status: ErrorStatus,
+
+    /// Optional additional details
+    ///
+    /// Boxed to keep the error (and so [`ParserResult`]) small, as most errors don't have any
+    details: Option<Box<ErrorDetails>>,
+}
+
+/// Whether a [`ParsingError`] is critical
+#[derive(Debug, Clone)]
+enum ErrorStatus {
+    /// Other parsers may be tried instead of the failed one
+    NonCritical {
+        /// Whether the error was already recorded in the parsing session
+        /// (see [`crate::ParsingSession::record_failure`])
+        recorded: bool,
+    },
+
+    /// The whole parsing fails, with the provided message
+    Critical(CriticalMessage),
+}
+
+/// Message of a critical [`ParsingError`]
+#[derive(Debug, Clone)]
+enum CriticalMessage {
+    /// Message provided by the user
+    Custom(Cow<'static, str>),
+
+    /// Message formatted when displayed, from the built-in error kind
+    /// if there is one, or from what was expected otherwise
+    Auto,
+}
+
+/// Optional details of a [`ParsingError`]
+#[derive(Debug, Clone, Default)]
+struct ErrorDetails {
     /// Optional atomic error
-    atomic_error: Option<&'static str>,
+    atomic_error: Option<Cow<'static, str>>,
 
-    /// Optional critical error emssage
-    critical: Option<Cow<'static, str>>,
+    /// Kind of the critical error, if it is a built-in one
+    critical_kind: Option<ErrorKind>,
+
+    /// Labelled parsers the error went through, from the innermost to the outermost
+    context: Vec<ContextFrame>,
+
+    /// Additional locations related to the error
+    secondary_labels: Vec<SecondaryLabel>,
+
+    /// Notes giving more information about the error
+    notes: Vec<Cow<'static, str>>,
+
+    /// Help messages indicating how to fix the error
+    help: Vec<Cow<'static, str>>,
+
+    /// Machine-applicable edits fixing the error
+    suggestions: Vec<Suggestion>,
+
+    /// Stable identifier of the error (e.g. "E0042")
+    code: Option<Cow<'static, str>>,
+
+    /// User-provided error that caused this one
+    source: Option<Arc<dyn Error + Send + Sync>>,
 }
 
 impl ParsingError {
@@ -26,8 +88,8 @@ impl ParsingError {
     pub const fn new(inner: ParsingErrorInner) -> Self {
         Self {
             inner,
-            atomic_error: None,
-            critical: None,
+            status: ErrorStatus::NonCritical { recorded: false },
+            details: None,
         }
     }
 
@@ -42,35 +104,324 @@ impl ParsingError {
     }
 
     /// Get the error's message if it is critical
-    pub fn critical_message(&self) -> Option<&str> {
-        self.critical.as_deref()
+    ///
+    /// Uses the [`DefaultFormatter`] if the message isn't a custom one.
+    pub fn critical_message(&self) -> Option<Cow<'_, str>> {
+        match &self.status {
+            ErrorStatus::NonCritical { .. } => None,
+            ErrorStatus::Critical(CriticalMessage::Custom(message)) => Some(Cow::Borrowed(message)),
+            ErrorStatus::Critical(CriticalMessage::Auto) => Some(self.message()),
+        }
     }
 
     /// Check if the error is critical
     pub const fn is_critical(&self) -> bool {
-        self.critical.is_some()
+        matches!(self.status, ErrorStatus::Critical(_))
     }
 
     /// Make the error critical, with the provided message
     pub fn criticalize(mut self, critical: impl Into<Cow<'static, str>>) -> Self {
-        if self.critical.is_none() {
-            self.critical = Some(critical.into());
+        if !self.is_critical() {
+            self.status = ErrorStatus::Critical(CriticalMessage::Custom(critical.into()));
+        }
+
+        self
+    }
+
+    /// Make the error critical, with a message describing what was expected
+    pub fn criticalize_auto(mut self) -> Self {
+        if !self.is_critical() {
+            self.status = ErrorStatus::Critical(CriticalMessage::Auto);
+        }
+
+        self
+    }
+
+    /// Make the error critical, with the message of a built-in error
+    pub fn criticalize_kind(mut self, kind: ErrorKind) -> Self {
+        if !self.is_critical() {
+            self.status = ErrorStatus::Critical(CriticalMessage::Auto);
+            self.details_mut().critical_kind = Some(kind);
+        }
+
+        self
+    }
+
+    /// Get the kind of the critical error, if it is a built-in one
+    pub fn critical_kind(&self) -> Option<&ErrorKind> {
+        self.details.as_ref()?.critical_kind.as_ref()
+    }
+
+    /// Get a human-readable message describing the error
+    ///
+    /// This is the critical message if there is one, or a description of what was expected otherwise.
+    ///
+    /// Uses the [`DefaultFormatter`] (see [`ParsingError::message_with`]).
+    pub fn message(&self) -> Cow<'_, str> {
+        self.message_with(&DefaultFormatter)
+    }
+
+    /// Get a human-readable message describing the error, using the provided formatter
+    ///
+    /// See [`ParsingError::message`]
+    pub fn message_with(&self, formatter: &dyn MessageFormatter) -> Cow<'_, str> {
+        if let Some(kind) = self.critical_kind() {
+            return Cow::Owned(formatter.kind(kind));
+        }
+
+        if let ErrorStatus::Critical(CriticalMessage::Custom(message)) = &self.status {
+            return Cow::Borrowed(message);
         }
 
+        let expected = formatter.expectation(self.inner.expected());
+
+        Cow::Owned(match &self.inner.found {
+            Some(found) => formatter.expected_found(&expected, &formatter.found(found)),
+            None => expected,
+        })
+    }
+
+    /// Indicate what the parser found instead of what it expected
+    pub fn with_found(mut self, found: Found) -> Self {
+        self.inner.found = Some(found);
         self
     }
 
     /// Get the error's message if it is atomic
-    pub const fn atomic_error(&self) -> Option<&'static str> {
-        self.atomic_error
+    pub fn atomic_error(&self) -> Option<&str> {
+        self.details.as_ref()?.atomic_error.as_deref()
     }
 
     /// make the error atomic
-    pub const fn with_atomic_error(mut self, atomic_err: &'static str) -> Self {
-        self.atomic_error = Some(atomic_err);
+    pub fn with_atomic_error(mut self, atomic_err: impl Into<Cow<'static, str>>) -> Self {
+        self.details_mut().atomic_error = Some(atomic_err.into());
         self
     }
 
+    /// Get the labelled parsers the error went through, from the innermost to the outermost
+    ///
+    /// See [`crate::ParserConstUtils::labelled`]
+    pub fn context(&self) -> &[ContextFrame] {
+        match &self.details {
+            Some(details) => &details.context,
+            None => &[],
+        }
+    }
+
+    /// Add a frame to the error's context
+    ///
+    /// The frame is considered to be the outermost one
+    pub fn with_context_frame(mut self, frame: ContextFrame) -> Self {
+        self.details_mut().context.push(frame);
+
+        // The recorded error doesn't have the new frame
+        if let ErrorStatus::NonCritical { recorded } = &mut self.status {
+            *recorded = false;
+        }
+
+        self
+    }
+
+    /// Get the additional locations related to the error
+    pub fn secondary_labels(&self) -> &[SecondaryLabel] {
+        match &self.details {
+            Some(details) => &details.secondary_labels,
+            None => &[],
+        }
+    }
+
+    /// Point at an additional location related to the error (e.g. "opening '{' here")
+    pub fn with_secondary_label(mut self, at: InputRange, message: impl Into<Message>) -> Self {
+        self.details_mut().secondary_labels.push(SecondaryLabel {
+            at,
+            message: message.into(),
+        });
+
+        self
+    }
+
+    /// Get the error's notes
+    pub fn notes(&self) -> &[Cow<'static, str>] {
+        match &self.details {
+            Some(details) => &details.notes,
+            None => &[],
+        }
+    }
+
+    /// Add a note giving more information about the error
+    pub fn with_note(mut self, note: impl Into<Cow<'static, str>>) -> Self {
+        self.details_mut().notes.push(note.into());
+        self
+    }
+
+    /// Get the error's help messages
+    pub fn help(&self) -> &[Cow<'static, str>] {
+        match &self.details {
+            Some(details) => &details.help,
+            None => &[],
+        }
+    }
+
+    /// Add a help message indicating how to fix the error
+    pub fn with_help(mut self, help: impl Into<Cow<'static, str>>) -> Self {
+        self.details_mut().help.push(help.into());
+        self
+    }
+
+    /// Get the edits suggested to fix the error
+    pub fn suggestions(&self) -> &[Suggestion] {
+        match &self.details {
+            Some(details) => &details.suggestions,
+            None => &[],
+        }
+    }
+
+    /// Suggest a machine-applicable edit fixing the error (e.g. "insert ';' here")
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.details_mut().suggestions.push(suggestion);
+        self
+    }
+
+    /// Apply the edits suggested to fix the error to the input it happened in
+    ///
+    /// See [`apply_suggestions`]
+    pub fn apply_suggestions(&self, input: &str) -> Result<String, ApplySuggestionsErr> {
+        apply_suggestions(input, self.suggestions())
+    }
+
+    /// Suggest the expected string closest to the word that was found instead, if any
+    ///
+    /// For instance, if `'return'` or `'break'` were expected and `'retrun'` was found,
+    /// a [`Suggestion`] replacing it with `'return'` is added.
+    ///
+    /// See [`ParserExpectation::closest_str`]
+    pub fn with_closest_str_suggestion(self) -> Self {
+        let Some(Found::Token(word)) = &self.inner.found else {
+            return self;
+        };
+
+        let Some(candidate) = self.inner.expected.closest_str(word) else {
+            return self;
+        };
+
+        let at = self.inner.at.start.range(word.len());
+
+        self.with_suggestion(Suggestion::replace(
+            at,
+            candidate,
+            Message::DidYouMean {
+                candidate: Cow::Borrowed(candidate),
+            },
+        ))
+    }
+
+    /// Get the error's code
+    pub fn code(&self) -> Option<&str> {
+        self.details.as_ref()?.code.as_deref()
+    }
+
+    /// Set the error's code, a stable identifier (e.g. "E0042") tools can match on
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.details_mut().code = Some(code.into());
+        self
+    }
+
+    /// Get the user-provided error that caused this one
+    pub fn source_error(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.details.as_ref()?.source.as_deref()
+    }
+
+    /// Get the user-provided error that caused this one, if it has the provided type
+    pub fn downcast_source<E: Error + 'static>(&self) -> Option<&E> {
+        self.source_error()?.downcast_ref()
+    }
+
+    /// Attach a user-provided error that caused this one
+    ///
+    /// It can be retrieved with [`ParsingError::source_error`] or [`ParsingError::downcast_source`]
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.details_mut().source = Some(Arc::new(source));
+        self
+    }
+
+    /// Create an error caused by a user-provided one, using its message
+    pub fn from_source(range: InputRange, source: impl Error + Send + Sync + 'static) -> Self {
+        let source: Arc<dyn Error + Send + Sync> = Arc::new(source);
+
+        let mut err = ParsingError::new(ParsingErrorInner::new(
+            range,
+            ParserExpectation::Dynamic(source.clone()),
+        ));
+
+        err.details_mut().source = Some(source);
+        err
+    }
+
+    /// Get the messages of the chain of user-provided errors that caused this one,
+    /// skipping the ones whose message is already displayed
+    pub(crate) fn source_causes(&self) -> Vec<String> {
+        let message = self.message();
+
+        let mut causes = vec![];
+        let mut source = self.source_error().map(|source| source as &dyn Error);
+
+        while let Some(err) = source {
+            let err_msg = err.to_string();
+
+            if err_msg != message {
+                causes.push(err_msg);
+            }
+
+            source = err.source();
+        }
+
+        causes
+    }
+
+    /// Map all the locations of the error (including its context, secondary labels and suggestions)
+    ///
+    /// Useful to convert locations from one coordinate system to another,
+    /// e.g. with [`ParserInput::source_range`] for token inputs
+    pub fn map_ranges(mut self, func: impl Fn(InputRange) -> InputRange) -> Self {
+        self.inner.at = func(self.inner.at);
+
+        if let Some(details) = &mut self.details {
+            for frame in &mut details.context {
+                frame.at = func(frame.at.range(0)).start;
+            }
+
+            for label in &mut details.secondary_labels {
+                label.at = func(label.at);
+            }
+
+            for suggestion in &mut details.suggestions {
+                suggestion.at = func(suggestion.at);
+            }
+        }
+
+        self
+    }
+
+    /// Check if the error has any details (context, secondary labels, notes, ...)
+    pub(crate) const fn has_details(&self) -> bool {
+        self.details.is_some()
+    }
+
+    /// Mark the error as recorded in the parsing session, returning if it already was
+    pub(crate) const fn mark_recorded(&mut self) -> bool {
+        match &mut self.status {
+            ErrorStatus::NonCritical { recorded } => std::mem::replace(recorded, true),
+
+            // Critical errors are never recorded
+            ErrorStatus::Critical(_) => true,
+        }
+    }
+
+    /// Get the error's details, creating them if required
+    fn details_mut(&mut self) -> &mut ErrorDetails {
+        self.details.get_or_insert_default()
+    }
+
     /// Create an error stating a specific character was expected
     pub const fn expected_char(range: InputRange, expected: char) -> ParsingError {
         ParsingError::new(ParsingErrorInner::new(
@@ -87,14 +438,102 @@ impl ParsingError {
         ))
     }
 
+    /// Create an error stating a specific byte was expected
+    pub const fn expected_byte(range: InputRange, expected: u8) -> ParsingError {
+        ParsingError::new(ParsingErrorInner::new(
+            range,
+            ParserExpectation::Byte(expected),
+        ))
+    }
+
+    /// Create an error stating a specific sequence of bytes was expected
+    pub const fn expected_bytes(range: InputRange, expected: &'static [u8]) -> ParsingError {
+        ParsingError::new(ParsingErrorInner::new(
+            range,
+            ParserExpectation::Bytes(expected),
+        ))
+    }
+
+    /// Create an error from a built-in error kind
+    pub const fn from_kind(range: InputRange, kind: ErrorKind) -> ParsingError {
+        ParsingError::new(ParsingErrorInner::new(range, ParserExpectation::Kind(kind)))
+    }
+
     /// Create an error with a custom message
-    pub const fn custom(range: InputRange, message: &'static str) -> ParsingError {
+    pub fn custom(range: InputRange, message: impl Into<Cow<'static, str>>) -> ParsingError {
         ParsingError::new(ParsingErrorInner::new(
             range,
-            ParserExpectation::Custom(message),
+            ParserExpectation::Custom(message.into()),
         ))
     }
 
+    /// Create an error with a message that is only formatted when displayed
+    ///
+    /// Useful to carry data in the error without formatting it eagerly
+    pub fn dynamic(
+        range: InputRange,
+        message: impl fmt::Display + Send + Sync + 'static,
+    ) -> ParsingError {
+        ParsingError::new(ParsingErrorInner::new(
+            range,
+            ParserExpectation::Dynamic(Arc::new(message)),
+        ))
+    }
+
+    /// Merge this error with the one returned by an alternative parser
+    ///
+    /// If one of the two errors happened further in the input, it is kept as is.
+    /// Otherwise, their expectations are merged into a single one.
+    pub fn merge_alternative(self, other: ParsingError) -> ParsingError {
+        let (at, other_at) = (self.inner.at, other.inner.at);
+
+        if at.start.offset > other_at.start.offset {
+            return self;
+        }
+
+        if other_at.start.offset > at.start.offset {
+            return other;
+        }
+
+        // Keep the most detailed context
+        let mut details = if self.details.is_none() || other.context().len() > self.context().len()
+        {
+            other.details
+        } else {
+            self.details
+        };
+
+        // The merged error is not atomic anymore
+        if let Some(details) = &mut details {
+            details.atomic_error = None;
+        }
+
+        // Prefer the most detailed description of what was found
+        let found = match (self.inner.found, other.inner.found) {
+            (None | Some(Found::Char(_)), Some(found @ Found::Token(_))) => Some(found),
+            (found, other_found) => found.or(other_found),
+        };
+
+        let mut merged = ParsingError::new(ParsingErrorInner {
+            at: InputRange::new(at.start, at.len.max(other_at.len)),
+            expected: self.inner.expected.merge(other.inner.expected),
+            found,
+        });
+
+        merged.details = details;
+
+        // Both errors' expectations are already merged in the session if they were recorded
+        if let (
+            ErrorStatus::NonCritical { recorded: true },
+            ErrorStatus::NonCritical { recorded: true },
+        ) = (self.status, other.status)
+        {
+            merged.mark_recorded();
+        }
+
+        merged
+    }
+
     /// Create an error that is not actually an error, but a breakage indicator
     ///
     /// See [`ParserExpectation::Break`]
@@ -106,8 +545,82 @@ impl ParsingError {
     }
 }
 
+impl fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatter = DefaultFormatter;
+        let context = self.context();
+
+        for (i, frame) in context.iter().rev().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", formatter.context_frame(frame, None))?;
+        }
+
+        if !context.is_empty() {
+            write!(f, ": ")?;
+        }
+
+        write!(f, "{}", self.message_with(&formatter))
+    }
+}
+
+impl Error for ParsingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source_error()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+/// Frame of a [`ParsingError`]'s context
+///
+/// See [`crate::ParserConstUtils::labelled`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextFrame {
+    /// Label of the parser
+    pub label: Cow<'static, str>,
+
+    /// Location the labelled parser started at
+    pub at: InputLocation,
+}
+
+impl ContextFrame {
+    /// Describe the frame, using the provided input to compute its line number
+    ///
+    /// Uses the [`DefaultFormatter`].
+    pub fn describe(&self, input: &str) -> String {
+        self.describe_with(&LineIndex::new(input))
+    }
+
+    /// Describe the frame, using the index of the input's lines to compute its line number
+    ///
+    /// See [`ContextFrame::describe`]
+    pub fn describe_with(&self, index: &LineIndex) -> String {
+        let line = self
+            .at
+            .compute_coords_with(index)
+            .ok()
+            .map(|coords| coords.line + 1);
+
+        DefaultFormatter.context_frame(self, line)
+    }
+}
+
+/// Additional location related to a [`ParsingError`]
+///
+/// See [`ParsingError::with_secondary_label`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondaryLabel {
+    /// Location in the input
+    pub at: InputRange,
+
+    /// Label's message
+    pub message: Message,
+}
+
 /// Inner content of a parsing error
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[must_use]
 pub struct ParsingErrorInner {
     /// Location of the error
@@ -115,12 +628,25 @@ pub struct ParsingErrorInner {
 
     /// Failed parser's expectation
     expected: ParserExpectation,
+
+    /// What the parser found instead
+    found: Option<Found>,
 }
 
 impl ParsingErrorInner {
     /// Create a [`ParsingError`]'s inner content
     pub const fn new(at: InputRange, expected: ParserExpectation) -> Self {
-        Self { at, expected }
+        Self {
+            at,
+            expected,
+            found: None,
+        }
+    }
+
+    /// Indicate what the parser found instead of what it expected
+    pub fn with_found(mut self, found: Found) -> Self {
+        self.found = Some(found);
+        self
     }
 
     /// Get the error's location in the source code
@@ -137,10 +663,102 @@ impl ParsingErrorInner {
     pub const fn expected(&self) -> &ParserExpectation {
         &self.expected
     }
+
+    /// Get what the parser found instead of what it expected, if known
+    pub const fn found(&self) -> Option<&Found> {
+        self.found.as_ref()
+    }
+}
+
+/// What a parser found instead of what it expected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found {
+    /// A single character
+    Char(char),
+
+    /// A word-like sequence of characters
+    Token(Box<str>),
+
+    /// A byte that is not part of a valid UTF-8 character
+    Byte(u8),
+
+    /// The end of the input
+    EndOfInput,
+}
+
+impl Found {
+    /// Describe the first character of the provided input
+    pub fn next_char_in(input: &str) -> Self {
+        match input.chars().next() {
+            Some(c) => Self::Char(c),
+            None => Self::EndOfInput,
+        }
+    }
+
+    /// Describe the next character of a parser's input,
+    /// or its next byte if it isn't part of a valid UTF-8 character
+    ///
+    /// For inputs made of tokens, the next token is described instead (see [`ParserInput::new_tokens`])
+    pub fn next_at<I: InputKind>(input: &ParserInput<I>) -> Self {
+        input.source().found_at(input.offset())
+    }
+
+    /// Describe the next byte of a parser's input
+    pub fn next_byte_at<I: InputKind<Source: ByteSource>>(input: &ParserInput<I>) -> Self {
+        match input.remaining_bytes().first() {
+            Some(byte) => Self::Byte(*byte),
+            None => Self::EndOfInput,
+        }
+    }
+
+    /// Describe the first token of a parser's input
+    ///
+    /// See [`Found::next_token_in`]
+    pub fn next_token_at<I: InputKind<Source: ByteSource>>(input: &ParserInput<I>) -> Self {
+        // Only look at the next bytes of binary inputs, to avoid validating their whole content
+        let text = match input.is_binary() {
+            true => input
+                .truncated(input.remaining_bytes().len().min(64))
+                .unwrap()
+                .inner(),
+            false => input.inner(),
+        };
+
+        match text.is_empty() {
+            true => Self::next_byte_at(input),
+            false => Self::next_token_in(text),
+        }
+    }
+
+    /// Describe the first token of the provided input
+    ///
+    /// If the input starts with an alphanumeric character or an underscore, the token is made
+    /// of all the following alphanumeric characters and underscores. Otherwise, it is the first character.
+    pub fn next_token_in(input: &str) -> Self {
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+        let len = input.find(|c| !is_word_char(c)).unwrap_or(input.len());
+
+        match input[..len].chars().nth(1) {
+            Some(_) => Self::Token(input[..len].into()),
+            None => Self::next_char_in(input),
+        }
+    }
+}
+
+impl fmt::Display for Found {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(c) => write!(f, "{c:?}"),
+            Self::Token(token) => write!(f, "'{token}'"),
+            Self::Byte(byte) => write!(f, "byte 0x{byte:02X}"),
+            Self::EndOfInput => write!(f, "end of input"),
+        }
+    }
 }
 
 /// Type of parser expectation in an error
-#[derive(Debug)]
+#[derive(Clone)]
 pub enum ParserExpectation {
     /// The parser expected a specific character
     Char(char),
@@ -148,8 +766,27 @@ pub enum ParserExpectation {
     /// The parser expected a specific string
     Str(&'static str),
 
+    /// The parser expected a specific byte
+    Byte(u8),
+
+    /// The parser expected a specific sequence of bytes
+    Bytes(&'static [u8]),
+
     /// Custom error message
-    Custom(&'static str),
+    Custom(Cow<'static, str>),
+
+    /// Built-in error
+    Kind(ErrorKind),
+
+    /// Custom error message, formatted when displayed
+    ///
+    /// Two dynamic expectations are only considered equal if they share the same allocation
+    Dynamic(Arc<dyn fmt::Display + Send + Sync>),
+
+    /// The parser expected any of the provided alternatives
+    ///
+    /// Never nested, and always contains at least two distinct expectations
+    OneOf(Vec<ParserExpectation>),
 
     /// This is not actually an error, but an indicator that the
     /// current parser chain should abort. This being inside an error
@@ -157,15 +794,147 @@ pub enum ParserExpectation {
     Break,
 }
 
+impl ParserExpectation {
+    /// Merge two expectations into one, flattening alternatives and removing duplicates
+    pub fn merge(self, other: ParserExpectation) -> ParserExpectation {
+        let mut alternatives = match self {
+            Self::OneOf(alternatives) => alternatives,
+            expectation => vec![expectation],
+        };
+
+        let others = match other {
+            Self::OneOf(alternatives) => alternatives,
+            expectation => vec![expectation],
+        };
+
+        for other in others {
+            if !alternatives.contains(&other) {
+                alternatives.push(other);
+            }
+        }
+
+        if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Self::OneOf(alternatives)
+        }
+    }
+
+    /// Check if this expectation already covers another one
+    pub fn includes(&self, other: &ParserExpectation) -> bool {
+        match (self, other) {
+            (Self::OneOf(alternatives), Self::OneOf(others)) => {
+                others.iter().all(|other| alternatives.contains(other))
+            }
+            (Self::OneOf(alternatives), other) => alternatives.contains(other),
+            (expectation, other) => expectation == other,
+        }
+    }
+
+    /// Find the expected string that is the closest to the provided word
+    ///
+    /// Only strings that are close enough (in terms of edit distance) are considered,
+    /// so that unrelated words don't get a suggestion.
+    pub fn closest_str(&self, word: &str) -> Option<&'static str> {
+        let candidates = match self {
+            Self::Str(_) => std::slice::from_ref(self),
+            Self::OneOf(alternatives) => alternatives.as_slice(),
+            _ => return None,
+        };
+
+        // Same threshold as the Rust compiler's suggestions
+        let max_distance = word.chars().count().max(3) / 3;
+
+        candidates
+            .iter()
+            .filter_map(|candidate| match candidate {
+                Self::Str(str) => Some((*str, edit_distance(word, str))),
+                _ => None,
+            })
+            .filter(|(_, distance)| *distance > 0 && *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(str, _)| str)
+    }
+}
+
 impl fmt::Display for ParserExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", DefaultFormatter.expectation(self))
+    }
+}
+
+impl fmt::Debug for ParserExpectation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Char(c) => write!(f, "expected character '{c}'"),
-            Self::Str(str) => write!(f, "expected string '{str}'"),
-            Self::Custom(custom) => write!(f, "{custom}"),
-            Self::Break => {
-                write!(f, "parser returned a break instruction")
+            Self::Char(c) => f.debug_tuple("Char").field(c).finish(),
+            Self::Str(str) => f.debug_tuple("Str").field(str).finish(),
+            Self::Byte(byte) => f.debug_tuple("Byte").field(byte).finish(),
+            Self::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            Self::Custom(custom) => f.debug_tuple("Custom").field(custom).finish(),
+            Self::Kind(kind) => f.debug_tuple("Kind").field(kind).finish(),
+            Self::Dynamic(dynamic) => f
+                .debug_tuple("Dynamic")
+                .field(&dynamic.to_string())
+                .finish(),
+            Self::OneOf(alternatives) => f.debug_tuple("OneOf").field(alternatives).finish(),
+            Self::Break => write!(f, "Break"),
+        }
+    }
+}
+
+impl PartialEq for ParserExpectation {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Char(a), Self::Char(b)) => a == b,
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::Byte(a), Self::Byte(b)) => a == b,
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (Self::Custom(a), Self::Custom(b)) => a == b,
+            (Self::Kind(a), Self::Kind(b)) => a == b,
+            (Self::Dynamic(a), Self::Dynamic(b)) => Arc::ptr_eq(a, b),
+            (Self::OneOf(a), Self::OneOf(b)) => a == b,
+            (Self::Break, Self::Break) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ParserExpectation {}
+
+/// Compute the edit distance between two strings
+///
+/// Counts insertions, deletions, substitutions and transpositions of adjacent characters
+/// (optimal string alignment distance), as swapping two characters is a common typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // distances[i][j] is the distance between the first i chars of a and the first j chars of b
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
             }
+
+            distances[i][j] = distance;
         }
     }
+
+    distances[a.len()][b.len()]
 }
@@ -1,64 +1,476 @@
-use std::any::Any;
+use std::{borrow::Cow, fmt, marker::PhantomData, ops::Range};
 
-use crate::{FileId, InputLocation, InputRange, Span};
+use perfect_derive::perfect_derive;
 
-/// Input provided to a [`crate::Parser`]
-#[derive(Debug, Clone, Copy)]
-pub struct ParserInput<'a> {
-    /// Input's current content, cut from the left when consumed by parsers
-    str: &'a str,
+use crate::{
+    Diagnostic, FileId, Found, InputLocation, InputRange, ParserResult, ParsingError,
+    ParsingSession, Span, UserState,
+};
 
+/// Input provided to a [`crate::Parser`]
+///
+/// It is either textual (see [`Self::new`]), binary (see [`Self::new_bytes`])
+/// or made of tokens (see [`Self::new_tokens`]), as described by its [`InputKind`].
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct ParserInput<'a, I: InputKind = TextInput> {
     /// Current location of the input
     at: InputLocation,
 
-    /// Input's original content, from which [`Self::str`] is a part of
-    original: &'a str,
+    /// Input's original content
+    original: &'a I::Source,
 
-    /// Parser's context (see [`Self::new_with_ctx`])
-    ctx: Option<fn() -> Box<dyn Any>>,
+    /// Parser's context (see [`Self::with_context`])
+    ctx: I::Context,
+
+    /// Parsing session (see [`Self::session`])
+    session: &'a ParsingSession<I::State>,
 }
 
-impl<'a> ParserInput<'a> {
-    /// Create a new input for parsers
-    pub const fn new(str: &'a str, file_id: FileId) -> Self {
-        Self {
-            str,
-            at: InputLocation { file_id, offset: 0 },
-            original: str,
-            ctx: None,
+/// Kind of input a [`crate::Parser`] works on
+///
+/// Parsers are generic over it, so they can only be used on the inputs they support:
+/// textual and binary parsers on text and bytes, token parsers on tokens of their type, etc.
+pub trait InputKind {
+    /// Content of the input
+    type Source: ?Sized + InputSource;
+
+    /// Context attached to the input (see [`ParserInput::with_context`])
+    type Context: Copy;
+
+    /// User state of the parsing session attached to the input (see [`ParsingSession::with_state`])
+    type State: UserState;
+
+    /// Same kind of input, with a context of the provided type
+    type WithContext<D: Copy>: InputKind<Source = Self::Source, Context = D, State = Self::State>;
+}
+
+/// [`InputKind`] of inputs whose content, context and user state have the provided types
+pub struct InputOf<S: ?Sized = str, C = (), St = ()>(PhantomData<KindOf<S, C, St>>);
+
+/// Marker of an [`InputOf`]'s types, which doesn't own any of them
+type KindOf<S, C, St> = fn() -> (*const S, C, St);
+
+impl<S: ?Sized + InputSource, C: Copy, St: UserState> InputKind for InputOf<S, C, St> {
+    type Source = S;
+    type Context = C;
+    type State = St;
+    type WithContext<D: Copy> = InputOf<S, D, St>;
+}
+
+/// Textual input (see [`ParserInput::new`])
+pub type TextInput = InputOf<str>;
+
+/// Binary input (see [`ParserInput::new_bytes`])
+pub type BinaryInput = InputOf<[u8]>;
+
+/// Input made of tokens produced by a lexer (see [`ParserInput::new_tokens`])
+pub type TokenInput<T> = InputOf<[Span<T>]>;
+
+/// Content a [`ParserInput`] can be made of
+///
+/// Implemented for strings, slices of bytes and slices of tokens (each token
+/// carrying its location in the source). Offsets count bytes for the former two,
+/// and tokens for the latter.
+pub trait InputSource: fmt::Debug {
+    /// Get the length of the content
+    fn len(&self) -> usize;
+
+    /// Check if the content is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the `len` first bytes (or tokens) of the content
+    ///
+    /// Returns [`None`] if the content is shorter, or if the prefix would end inside a character
+    fn prefix(&self, len: usize) -> Option<&Self>;
+
+    /// Get the length of the character, byte or token starting at the provided offset
+    fn item_len_at(&self, offset: usize) -> Option<usize>;
+
+    /// Describe the character, byte or token starting at the provided offset
+    fn found_at(&self, offset: usize) -> Found;
+
+    /// Describe a range of the content, for error messages
+    fn describe(&self, range: Range<usize>) -> Cow<'_, str>;
+
+    /// Convert a range of the content to the range it covers in the source
+    ///
+    /// See [`ParserInput::source_range`]
+    fn source_range(&self, range: InputRange) -> InputRange {
+        range
+    }
+}
+
+/// Content made of bytes, either textual or binary
+///
+/// Textual and binary parsers can be used on inputs made of such content.
+pub trait ByteSource: InputSource {
+    /// Get the content as bytes
+    fn as_bytes(&self) -> &[u8];
+
+    /// Get the content as text, if it is textual
+    fn as_text(&self) -> Option<&str>;
+}
+
+impl InputSource for str {
+    fn len(&self) -> usize {
+        str::len(self)
+    }
+
+    fn prefix(&self, len: usize) -> Option<&Self> {
+        self.get(..len)
+    }
+
+    fn item_len_at(&self, offset: usize) -> Option<usize> {
+        self.get(offset..)?.chars().next().map(char::len_utf8)
+    }
+
+    fn found_at(&self, offset: usize) -> Found {
+        Found::next_char_in(&self[offset..])
+    }
+
+    fn describe(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+}
+
+impl ByteSource for str {
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl InputSource for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn prefix(&self, len: usize) -> Option<&Self> {
+        self.get(..len)
+    }
+
+    fn item_len_at(&self, offset: usize) -> Option<usize> {
+        let bytes = self.get(offset..)?;
+
+        match decode_char(bytes) {
+            Some(c) => Some(c.len_utf8()),
+            None => (!bytes.is_empty()).then_some(1),
+        }
+    }
+
+    fn found_at(&self, offset: usize) -> Found {
+        let bytes = &self[offset..];
+
+        match (decode_char(bytes), bytes.first()) {
+            (Some(c), _) => Found::Char(c),
+            (None, Some(byte)) => Found::Byte(*byte),
+            (None, None) => Found::EndOfInput,
+        }
+    }
+
+    fn describe(&self, range: Range<usize>) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self[range])
+    }
+}
+
+impl ByteSource for [u8] {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<T: fmt::Debug> InputSource for [Span<T>] {
+    fn len(&self) -> usize {
+        <[Span<T>]>::len(self)
+    }
+
+    fn prefix(&self, len: usize) -> Option<&Self> {
+        self.get(..len)
+    }
+
+    fn item_len_at(&self, offset: usize) -> Option<usize> {
+        (offset < self.len()).then_some(1)
+    }
+
+    fn found_at(&self, offset: usize) -> Found {
+        match self.get(offset) {
+            Some(token) => Found::Token(format!("{:?}", token.data).into()),
+            None => Found::EndOfInput,
         }
     }
 
+    fn describe(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Owned(
+            self[range]
+                .iter()
+                .map(|token| format!("{:?}", token.data))
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// For tokens, this is the range from the first token's start to the last one's end.
+    /// Empty ranges are located at the start of the next token, or at the end of the last one.
+    fn source_range(&self, range: InputRange) -> InputRange {
+        let InputRange { start, len } = range;
+
+        if len > 0
+            && let Some(first) = self.get(start.offset)
+            && let Some(last) = self.get(start.offset + len - 1)
+        {
+            return InputRange::new(
+                first.at.start,
+                (last.at.start.offset + last.at.len).saturating_sub(first.at.start.offset),
+            );
+        }
+
+        if let Some(next) = self.get(start.offset) {
+            return next.at.start.range(0);
+        }
+
+        match start.offset.checked_sub(1).and_then(|prev| self.get(prev)) {
+            Some(prev) => prev.at.start.add(prev.at.len).range(0),
+            None => InputLocation {
+                file_id: start.file_id,
+                offset: 0,
+            }
+            .range(0),
+        }
+    }
+}
+
+impl<'a, St: UserState> ParserInput<'a, InputOf<str, (), St>> {
+    /// Create a new input for parsers
+    ///
+    /// The parsing session records what happens during parsing (see [`ParsingSession`]),
+    /// and the type of its user state becomes part of the input's [`InputKind`].
+    pub const fn new(str: &'a str, file_id: FileId, session: &'a ParsingSession<St>) -> Self {
+        Self::from_source(str, file_id, session)
+    }
+}
+
+impl<'a, C: Copy, St: UserState> ParserInput<'a, InputOf<str, C, St>> {
     /// Create a new input for parsers, with the provided context
     ///
-    /// The context can be of any type ; it can be fetched by any parser, even nested,
-    /// using the [`Self::ctx`] method.
+    /// See [`Self::with_context`]
+    pub const fn new_with_ctx(
+        str: &'a str,
+        file_id: FileId,
+        session: &'a ParsingSession<St>,
+        ctx: C,
+    ) -> Self {
+        ParserInput::new(str, file_id, session).with_context(ctx)
+    }
+}
+
+impl<'a, St: UserState> ParserInput<'a, InputOf<[u8], (), St>> {
+    /// Create a new binary input for parsers
+    ///
+    /// Textual parsers can still be used on it, and will only match valid UTF-8 content.
+    pub const fn new_bytes(
+        bytes: &'a [u8],
+        file_id: FileId,
+        session: &'a ParsingSession<St>,
+    ) -> Self {
+        Self::from_source(bytes, file_id, session)
+    }
+}
+
+impl<'a, T: fmt::Debug, St: UserState> ParserInput<'a, InputOf<[Span<T>], (), St>> {
+    /// Create a new input for parsers, made of tokens produced by a lexer
     ///
-    /// For easier handling, prefer using the [`get_context`](`crate::parsers::helpers::get_context`) helper function.
-    pub const fn new_with_ctx(str: &'a str, file_id: FileId, ctx: fn() -> Box<dyn Any>) -> Self {
+    /// Offsets of the input count tokens instead of bytes ; use [`Self::source_range`]
+    /// to convert a range of tokens to the range it covers in the source.
+    ///
+    /// Token parsers such as [`token`](`crate::parsers::helpers::token`) must be used on it.
+    pub fn new_tokens(tokens: &'a [Span<T>], session: &'a ParsingSession<St>) -> Self {
+        let file_id = tokens
+            .first()
+            .map_or(FileId::None, |token| token.at.start.file_id);
+
+        Self::from_source(tokens, file_id, session)
+    }
+}
+
+impl<'a, S: ?Sized + InputSource, St: UserState> ParserInput<'a, InputOf<S, (), St>> {
+    /// Create a new input for parsers, with the provided content
+    pub const fn from_source(
+        source: &'a S,
+        file_id: FileId,
+        session: &'a ParsingSession<St>,
+    ) -> Self {
         Self {
-            str,
             at: InputLocation { file_id, offset: 0 },
-            original: str,
-            ctx: Some(ctx),
+            original: source,
+            ctx: (),
+            session,
+        }
+    }
+}
+
+impl<'a, I: InputKind> ParserInput<'a, I> {
+    /// Attach a context to the input, replacing its current one
+    ///
+    /// The context's type is part of the input's [`InputKind`], so parsers fetching it
+    /// (e.g. with [`get_context`](`crate::parsers::helpers::get_context`)) can only be used
+    /// on inputs with a context of the expected type.
+    ///
+    /// To avoid copying a large context, provide a reference to it.
+    pub const fn with_context<D: Copy>(self, ctx: D) -> ParserInput<'a, I::WithContext<D>> {
+        ParserInput {
+            at: self.at,
+            original: self.original,
+            ctx,
+            session: self.session,
         }
     }
 
-    /// Get the raw context from the input
+    /// Get the parsing session attached to the input
+    pub const fn session(&self) -> &'a ParsingSession<I::State> {
+        self.session
+    }
+
+    /// Record a failure in the parsing session
+    pub fn record_failure(&self, err: &ParsingError) {
+        self.session.record_failure(err);
+    }
+
+    /// Emit a non-fatal diagnostic into the parsing session
+    ///
+    /// The diagnostic is discarded if the parser emitting it ends up failing.
+    pub fn emit_diagnostic(&self, diagnostic: Diagnostic) {
+        self.session.push_diagnostic(diagnostic);
+    }
+
+    /// Run a function while discarding all failures recorded during its execution
+    pub fn without_failure_tracking<T>(&mut self, func: impl FnOnce(&mut Self) -> T) -> T {
+        self.session.without_failure_tracking(|| func(self))
+    }
+
+    /// Run a parsing function, and discard everything it put in the parsing session
+    /// (diagnostics, recovered errors and user state updates) if it fails
+    ///
+    /// Parsers that carry on after one of their inner parsers failed (e.g. to try another one)
+    /// must use this to discard what the failed parser did.
+    /// Critical failures abort the whole parsing, so they don't trigger a rollback.
+    pub fn rollback_on_failure<T>(
+        &mut self,
+        func: impl FnOnce(&mut Self) -> ParserResult<T>,
+    ) -> ParserResult<T> {
+        let checkpoint = self.session.checkpoint();
+
+        func(self).inspect_err(|err| {
+            if !err.is_critical() {
+                self.session.rollback(checkpoint);
+            }
+        })
+    }
+
+    /// Read the user state of the parsing session
     ///
-    /// For easier manipulation (especially downcasting), prefer using the
-    /// [`get_context`](`crate::parsers::helpers::get_context`) helper function.
-    pub fn ctx(&self) -> Option<fn() -> Box<dyn Any>> {
+    /// See [`ParsingSession::with_state`]
+    pub fn state<R>(&self, func: impl FnOnce(&I::State) -> R) -> R {
+        self.session.state(func)
+    }
+
+    /// Update the user state of the parsing session
+    ///
+    /// The update will be undone if the parser that made it fails.
+    ///
+    /// See [`ParsingSession::with_state`] and [`Self::rollback_on_failure`]
+    pub fn update_state<R>(&self, func: impl FnOnce(&mut I::State) -> R) -> R {
+        self.session.update_state(func)
+    }
+
+    /// Get the context from the input (see [`Self::with_context`])
+    pub const fn context(&self) -> I::Context {
         self.ctx
     }
 
-    /// Get the current input's non-consumed content
+    /// Get the input's original content
     ///
-    /// When parsers consume the input, this content is sliced from the left
+    /// Content hidden by [`Self::truncated`] is not included.
+    pub const fn source(&self) -> &'a I::Source {
+        self.original
+    }
+
+    /// Check if the input has been consumed entirely
+    pub fn is_empty(&self) -> bool {
+        self.at.offset >= self.original.len()
+    }
+
+    /// Restrict the input to its `len` next bytes (or tokens), hiding everything after them
     ///
-    /// To get the original content, use [`Self::original`]
-    pub const fn inner(&self) -> &str {
-        self.str
+    /// Returns [`None`] if there are less than `len` bytes remaining,
+    /// or if the restricted content would end inside a character for a textual input.
+    pub fn truncated(&self, len: usize) -> Option<Self> {
+        let end = self.at.offset.checked_add(len)?;
+
+        Some(Self {
+            original: self.original.prefix(end)?,
+            ..*self
+        })
+    }
+
+    /// Advance the input by the provided span
+    pub fn advance(&mut self, from: InputRange) {
+        assert_eq!(
+            self.at, from.start,
+            "Provided span does not start at the same position as the input"
+        );
+
+        self.at = self.at.add(from.len);
+    }
+
+    /// Skip the `len` next bytes (or tokens) of the input
+    ///
+    /// If the provided length ends up inside a character boundary, or
+    /// if it exceeds the input's non-consumed content's length, a [`None`]
+    /// variant will be returned instead, and nothing will be skipped
+    pub fn try_skip(&mut self, len: usize) -> Option<InputRange> {
+        self.truncated(len)?;
+
+        let skipped = self.range(len);
+
+        self.at = self.at.add(len);
+
+        Some(skipped)
+    }
+
+    /// Skip the next character, byte or token of the input
+    ///
+    /// See [`InputSource::item_len_at`]
+    pub fn try_skip_item(&mut self) -> Option<InputRange> {
+        let len = self.original.item_len_at(self.at.offset)?;
+
+        self.try_skip(len)
+    }
+
+    /// Describe a range of the input, for error messages
+    ///
+    /// See [`InputSource::describe`]
+    pub fn describe(&self, range: InputRange) -> Cow<'a, str> {
+        self.original
+            .describe(range.start.offset..range.start.offset + range.len)
+    }
+
+    /// Convert a range of the input to the range it covers in the source
+    ///
+    /// For token inputs, this is the range from the first token's start to the last one's end.
+    /// Empty ranges are located at the start of the next token, or at the end of the last one.
+    ///
+    /// For other inputs, the range is returned as is.
+    pub fn source_range(&self, range: InputRange) -> InputRange {
+        self.original.source_range(range)
     }
 
     /// Get the current location of the input
@@ -76,23 +488,51 @@ impl<'a> ParserInput<'a> {
     pub const fn offset(&self) -> usize {
         self.at.offset()
     }
+}
+
+impl<'a, I: InputKind<Source: ByteSource>> ParserInput<'a, I> {
+    /// Get the current input's non-consumed content
+    ///
+    /// When parsers consume the input, this content is sliced from the left
+    ///
+    /// To get the original content, use [`Self::original`]
+    ///
+    /// For binary inputs, this is the longest valid UTF-8 prefix of the non-consumed content,
+    /// which requires validating all of it each time. Prefer using [`Self::remaining_bytes`],
+    /// [`Self::try_eat_char`] or a [`Self::truncated`] input for these.
+    pub fn inner(&self) -> &'a str {
+        match self.original.as_text() {
+            Some(str) => &str[self.at.offset..],
+            None => utf8_prefix(self.remaining_bytes()),
+        }
+    }
+
+    /// Get the current input's non-consumed content, as bytes
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        &self.original_bytes()[self.at.offset..]
+    }
+
+    /// Check if the input is binary (see [`Self::new_bytes`])
+    pub fn is_binary(&self) -> bool {
+        self.original.as_text().is_none()
+    }
 
     /// Get the parser's original content
     ///
     /// To get the non-consumed part, use [`Self::inner`]
-    pub const fn original(&self) -> &'a str {
-        self.original
+    ///
+    /// For binary inputs, this is the longest valid UTF-8 prefix of the original content,
+    /// which requires validating it. Prefer using [`Self::original_bytes`] for these.
+    pub fn original(&self) -> &'a str {
+        match self.original.as_text() {
+            Some(str) => str,
+            None => utf8_prefix(self.original.as_bytes()),
+        }
     }
 
-    /// Advance the input by the provided span
-    pub fn advance(&mut self, from: InputRange) {
-        assert_eq!(
-            self.at, from.start,
-            "Provided span does not start at the same position as the input"
-        );
-
-        self.at = self.at.add(from.len);
-        self.str = &self.str[from.len..];
+    /// Get the parser's original content, as bytes
+    pub fn original_bytes(&self) -> &'a [u8] {
+        self.original.as_bytes()
     }
 
     /// Consume the `len` next bytes from the input
@@ -100,17 +540,19 @@ impl<'a> ParserInput<'a> {
     /// If the provided length ends up inside a character boundary, or
     /// if it exceeds the input's non-consumed content's length, a [`None`]
     /// variant will be returned instead, and nothing will be consumed
-    pub fn try_eat(&mut self, len: usize) -> Option<Span<&str>> {
-        if len > self.str.len() || !self.str.is_char_boundary(len) {
-            return None;
-        }
+    ///
+    /// For binary inputs, the consumed bytes must also be valid UTF-8
+    pub fn try_eat(&mut self, len: usize) -> Option<Span<&'a str>> {
+        let data = match self.original.as_text() {
+            Some(str) => str.get(self.at.offset..)?.get(..len)?,
+            None => std::str::from_utf8(self.remaining_bytes().get(..len)?).ok()?,
+        };
 
         let ate = Span {
             at: self.range(len),
-            data: &self.str[..len],
+            data,
         };
 
-        self.str = &self.str[len..];
         self.at = self.at.add(len);
 
         Some(ate)
@@ -118,15 +560,115 @@ impl<'a> ParserInput<'a> {
 
     /// Consume the next character from the input
     pub fn try_eat_char(&mut self) -> Option<Span<char>> {
-        let char = self.str.chars().next()?;
+        let char = match self.original.as_text() {
+            Some(str) => str[self.at.offset..].chars().next()?,
+            None => decode_char(self.remaining_bytes())?,
+        };
 
         let ate = self.try_eat(char.len_utf8()).unwrap();
 
         Some(ate.forge_here(char))
     }
 
+    /// Consume the `len` next bytes from the input
+    ///
+    /// If it exceeds the input's non-consumed content's length, or if the provided length
+    /// ends up inside a character boundary for a textual input, a [`None`] variant will be
+    /// returned instead, and nothing will be consumed
+    pub fn try_eat_bytes(&mut self, len: usize) -> Option<Span<&'a [u8]>> {
+        if let Some(str) = self.original.as_text()
+            && !str.is_char_boundary(self.at.offset.checked_add(len)?)
+        {
+            return None;
+        }
+
+        let data = self.remaining_bytes().get(..len)?;
+
+        let ate = Span {
+            at: self.range(len),
+            data,
+        };
+
+        self.at = self.at.add(len);
+
+        Some(ate)
+    }
+
     /// Extract the part matching the provided [`InputRange`] from the input's original content
-    pub fn extract(&self, range: InputRange) -> &str {
-        &self.original[range.start.offset..range.start.offset + range.len]
+    ///
+    /// Panics if the extracted content is not valid UTF-8, which can only happen for binary inputs
+    pub fn extract(&self, range: InputRange) -> &'a str {
+        self.try_extract(range)
+            .expect("extracted content is not valid UTF-8")
+    }
+
+    /// Extract the part matching the provided [`InputRange`] from the input's original content
+    ///
+    /// Returns [`None`] if the extracted content is not valid UTF-8, which can only happen for binary inputs
+    pub fn try_extract(&self, range: InputRange) -> Option<&'a str> {
+        match self.original.as_text() {
+            Some(str) => Some(&str[range.start.offset..range.start.offset + range.len]),
+            None => std::str::from_utf8(self.extract_bytes(range)).ok(),
+        }
+    }
+
+    /// Extract the part matching the provided [`InputRange`] from the input's original content, as bytes
+    pub fn extract_bytes(&self, range: InputRange) -> &'a [u8] {
+        &self.original_bytes()[range.start.offset..range.start.offset + range.len]
+    }
+}
+
+impl<'a, T: fmt::Debug, I: InputKind<Source = [Span<T>]>> ParserInput<'a, I> {
+    /// Get the tokens the input is made of (see [`Self::new_tokens`])
+    ///
+    /// Tokens hidden by [`Self::truncated`] are not included.
+    pub const fn tokens(&self) -> &'a [Span<T>] {
+        self.original
+    }
+
+    /// Get the next token, without consuming it
+    ///
+    /// Returns [`None`] if the input has been consumed entirely
+    pub fn peek_token(&self) -> Option<&'a T> {
+        self.original.get(self.at.offset).map(|token| &token.data)
+    }
+
+    /// Consume the next token
+    ///
+    /// See [`Self::peek_token`]
+    pub fn try_eat_token(&mut self) -> Option<Span<&'a T>> {
+        let token = self.peek_token()?;
+
+        let ate = Span {
+            at: self.range(1),
+            data: token,
+        };
+
+        self.at = self.at.add(1);
+
+        Some(ate)
+    }
+}
+
+/// Decode the character some bytes start with, if they start with a valid UTF-8 character
+///
+/// Only the bytes the character is made of are decoded
+fn decode_char(bytes: &[u8]) -> Option<char> {
+    let len = match bytes.first()? {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return None,
+    };
+
+    std::str::from_utf8(bytes.get(..len)?).ok()?.chars().next()
+}
+
+/// Get the longest valid UTF-8 prefix of some bytes
+fn utf8_prefix(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(str) => str,
+        Err(err) => std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap(),
     }
 }
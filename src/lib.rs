@@ -5,14 +5,25 @@
 #![feature(const_trait_impl)]
 
 mod containers;
+mod diagnostic;
 mod error;
 mod input;
+mod line_index;
+mod message;
 mod parser;
+mod session;
+mod source_map;
 mod span;
+mod stream;
+mod structured;
+mod suggestion;
 
 pub mod parsers;
 
-pub use self::{containers::*, error::*, input::*, parser::*, span::*};
+pub use self::{
+    containers::*, diagnostic::*, error::*, input::*, line_index::*, message::*, parser::*,
+    session::*, source_map::*, span::*, stream::*, structured::*, suggestion::*,
+};
 
 #[cfg(feature = "error-reporting")]
 mod report;
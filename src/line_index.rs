@@ -0,0 +1,273 @@
+use std::ops::Range;
+
+use crate::{CoordsInString, InputRange, LocationOutOfBoundsErr};
+
+/// Index of the lines of an input, to convert offsets to coordinates (and back) efficiently
+///
+/// Built once per input in linear time, it then performs each conversion in logarithmic time.
+///
+/// Lines are separated by `\n`, a preceding `\r` not being considered part of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Offset of each line's first byte
+    starts: Vec<usize>,
+
+    /// Offset of each line's end, excluding the line break
+    ends: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Index the lines of the provided input
+    pub fn new(input: &str) -> Self {
+        let mut starts = vec![0];
+        let mut ends = vec![];
+
+        for (pos, _) in input.match_indices('\n') {
+            let end = if input[..pos].ends_with('\r') {
+                pos - 1
+            } else {
+                pos
+            };
+
+            ends.push(end);
+            starts.push(pos + 1);
+        }
+
+        ends.push(input.len());
+
+        Self { starts, ends }
+    }
+
+    /// Get the number of lines
+    ///
+    /// An empty input, or an input ending with a line break, ends with an empty line
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Get the length of the indexed input
+    pub fn len(&self) -> usize {
+        self.ends[self.ends.len() - 1]
+    }
+
+    /// Check if the indexed input is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the range of bytes covered by a line (starting at 0), excluding its line break
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        Some(*self.starts.get(line)?..self.ends[line])
+    }
+
+    /// Get the line (starting at 0) containing an offset
+    ///
+    /// The offset may point at the end of the input.
+    pub fn line_of(&self, offset: usize) -> Result<usize, LocationOutOfBoundsErr> {
+        if offset > self.len() {
+            return Err(LocationOutOfBoundsErr);
+        }
+
+        Ok(self.starts.partition_point(|start| *start <= offset) - 1)
+    }
+
+    /// Compute the coordinates of an offset
+    ///
+    /// The offset may point at the end of the input.
+    /// Offsets pointing at a line break are considered to be at the end of their line.
+    pub fn coords(&self, offset: usize) -> Result<CoordsInString, LocationOutOfBoundsErr> {
+        let line = self.line_of(offset)?;
+
+        Ok(CoordsInString {
+            line,
+            col: offset.min(self.ends[line]) - self.starts[line],
+        })
+    }
+
+    /// Compute the offset of coordinates
+    ///
+    /// The column may point at the end of the line.
+    pub fn offset(&self, coords: CoordsInString) -> Result<usize, LocationOutOfBoundsErr> {
+        let CoordsInString { line, col } = coords;
+
+        let range = self.line_range(line).ok_or(LocationOutOfBoundsErr)?;
+
+        if col > range.len() {
+            return Err(LocationOutOfBoundsErr);
+        }
+
+        Ok(range.start + col)
+    }
+
+    /// Compute the coordinates of an offset, with the column counted in the provided unit
+    ///
+    /// The provided input must be the indexed one.
+    ///
+    /// See [`LineIndex::coords`]
+    pub fn coords_in_unit(
+        &self,
+        input: &str,
+        offset: usize,
+        unit: ColumnUnit,
+    ) -> Result<CoordsInString, LocationOutOfBoundsErr> {
+        let CoordsInString { line, col } = self.coords(offset)?;
+
+        let line_start = self.starts[line];
+
+        let before = input
+            .get(line_start..line_start + col)
+            .ok_or(LocationOutOfBoundsErr)?;
+
+        Ok(CoordsInString {
+            line,
+            col: unit.measure(before),
+        })
+    }
+
+    /// Compute the offset of coordinates whose column is counted in the provided unit
+    ///
+    /// The provided input must be the indexed one.
+    /// Fails if the column points inside a character.
+    ///
+    /// See [`LineIndex::offset`]
+    pub fn offset_in_unit(
+        &self,
+        input: &str,
+        coords: CoordsInString,
+        unit: ColumnUnit,
+    ) -> Result<usize, LocationOutOfBoundsErr> {
+        let CoordsInString { line, col } = coords;
+
+        let range = self.line_range(line).ok_or(LocationOutOfBoundsErr)?;
+        let content = input.get(range.clone()).ok_or(LocationOutOfBoundsErr)?;
+
+        let mut counted = 0;
+
+        for (pos, c) in content.char_indices() {
+            if counted == col {
+                return Ok(range.start + pos);
+            }
+
+            if counted > col {
+                break;
+            }
+
+            counted += unit.measure_char(c);
+        }
+
+        if counted == col {
+            Ok(range.end)
+        } else {
+            Err(LocationOutOfBoundsErr)
+        }
+    }
+
+    /// Convert an offset to a position of the Language Server Protocol
+    ///
+    /// The provided input must be the indexed one.
+    pub fn lsp_position(
+        &self,
+        input: &str,
+        offset: usize,
+    ) -> Result<LspPosition, LocationOutOfBoundsErr> {
+        let CoordsInString { line, col } = self.coords_in_unit(input, offset, ColumnUnit::Utf16)?;
+
+        Ok(LspPosition {
+            line: u32::try_from(line).map_err(|_| LocationOutOfBoundsErr)?,
+            character: u32::try_from(col).map_err(|_| LocationOutOfBoundsErr)?,
+        })
+    }
+
+    /// Convert a range to a range of the Language Server Protocol
+    ///
+    /// The provided input must be the indexed one.
+    pub fn lsp_range(
+        &self,
+        input: &str,
+        at: InputRange,
+    ) -> Result<LspRange, LocationOutOfBoundsErr> {
+        Ok(LspRange {
+            start: self.lsp_position(input, at.start.offset)?,
+            end: self.lsp_position(input, at.start.offset + at.len)?,
+        })
+    }
+
+    /// Convert a position of the Language Server Protocol to an offset
+    ///
+    /// The provided input must be the indexed one.
+    pub fn offset_of_lsp_position(
+        &self,
+        input: &str,
+        position: LspPosition,
+    ) -> Result<usize, LocationOutOfBoundsErr> {
+        let LspPosition { line, character } = position;
+
+        self.offset_in_unit(
+            input,
+            CoordsInString {
+                line: line as usize,
+                col: character as usize,
+            },
+            ColumnUnit::Utf16,
+        )
+    }
+}
+
+/// Unit columns are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes, as used by [`CoordsInString`]
+    Bytes,
+
+    /// Unicode scalar values (Rust [`char`]s)
+    Chars,
+
+    /// UTF-16 code units, as used by the Language Server Protocol and most editors
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Measure the length of a string in this unit
+    pub fn measure(self, str: &str) -> usize {
+        match self {
+            Self::Bytes => str.len(),
+            Self::Chars => str.chars().count(),
+            Self::Utf16 => str.chars().map(char::len_utf16).sum(),
+        }
+    }
+
+    /// Measure the length of a character in this unit
+    pub fn measure_char(self, c: char) -> usize {
+        match self {
+            Self::Bytes => c.len_utf8(),
+            Self::Chars => 1,
+            Self::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+/// Position in a text document, as defined by the Language Server Protocol
+///
+/// The character offset is counted in UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LspPosition {
+    /// Line number (starting at 0)
+    pub line: u32,
+
+    /// Character offset in the line (starting at 0)
+    pub character: u32,
+}
+
+/// Range in a text document, as defined by the Language Server Protocol
+///
+/// The end position is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LspRange {
+    /// Start of the range
+    pub start: LspPosition,
+
+    /// End of the range
+    pub end: LspPosition,
+}
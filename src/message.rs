@@ -0,0 +1,317 @@
+use std::{borrow::Cow, fmt};
+
+use crate::{ContextFrame, Found, ParserExpectation, Severity};
+
+/// Kind of a built-in error, along with its arguments
+///
+/// Built-in parsers describe their failures with this type instead of plain text,
+/// so their messages can be customized with a [`MessageFormatter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A character did not satisfy a filter
+    CharFilter,
+
+    /// A character was not part of the expected set
+    CharInSet,
+
+    /// A digit in the provided radix was expected
+    Digit { radix: u32 },
+
+    /// A byte did not satisfy a filter
+    ByteFilter,
+
+    /// An integer of the provided size (in bytes) was expected
+    Int { size: usize },
+
+    /// A LEB128-encoded integer was expected
+    Leb128,
+
+    /// A LEB128-encoded integer does not fit in the expected type
+    Leb128Overflow,
+
+    /// A length-prefixed field is longer than the remaining input
+    TruncatedField { len: usize, remaining: usize },
+
+    /// A length-prefixed field was not consumed entirely
+    UnconsumedField { remaining: usize },
+
+    /// A field length does not fit in memory
+    FieldLengthOverflow,
+
+    /// The consumed content is not valid UTF-8
+    InvalidUtf8,
+
+    /// A specific token was expected
+    Token { token: Box<str> },
+
+    /// A token did not satisfy a filter
+    TokenFilter,
+
+    /// A whitespace character was expected
+    Whitespace,
+
+    /// At least one whitespace was expected
+    Whitespaces,
+
+    /// A newline was expected
+    Newline,
+
+    /// The start of the input was expected
+    StartOfInput,
+
+    /// The end of the input was expected
+    EndOfInput,
+
+    /// The end of the input was reached too early
+    UnexpectedEndOfInput,
+
+    /// An opening delimiter was never closed
+    UnclosedDelimiter { opener: Box<str> },
+
+    /// An opening delimiter was closed by a different delimiter
+    MismatchedDelimiter { opener: Box<str>, closer: char },
+
+    /// A record parser matched without consuming any input
+    EmptyRecord,
+
+    /// A parser matched while it should not have
+    UnexpectedMatch,
+
+    /// A parser did not match enough times
+    NotEnoughRepetitions { min: usize, found: usize },
+
+    /// A parser did not match after a separator
+    FailedAfterSeparator,
+
+    /// None of a choice's parsers matched
+    NoChoiceMatched,
+
+    /// A validator rejected the parsed value
+    ValidationFailed,
+
+    /// A mapping function returned an error
+    MappingFailed,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CharFilter => write!(f, "character filter failed"),
+            Self::CharInSet => write!(f, "character did not match against the provided set"),
+            Self::Digit { radix: 10 } => write!(f, "expected a digit"),
+            Self::Digit { radix } => write!(f, "expected a base {radix} digit"),
+            Self::ByteFilter => write!(f, "byte filter failed"),
+            Self::Int { size } => write!(f, "expected a {size}-byte integer"),
+            Self::Leb128 => write!(f, "expected a LEB128-encoded integer"),
+            Self::Leb128Overflow => write!(f, "LEB128-encoded integer is too large"),
+            Self::TruncatedField { len, remaining } => write!(
+                f,
+                "expected a field of {len} bytes, but only {remaining} are remaining"
+            ),
+            Self::UnconsumedField { remaining } => {
+                write!(f, "{remaining} unexpected bytes at the end of the field")
+            }
+            Self::FieldLengthOverflow => write!(f, "field length is too large"),
+            Self::InvalidUtf8 => write!(f, "expected valid UTF-8 content"),
+            Self::Token { token } => write!(f, "expected token {token}"),
+            Self::TokenFilter => write!(f, "token filter failed"),
+            Self::Whitespace => write!(f, "expected a whitespace"),
+            Self::Whitespaces => write!(f, "expected at least one whitespace"),
+            Self::Newline => write!(f, "expected a newline"),
+            Self::StartOfInput => write!(f, "expected start of input"),
+            Self::EndOfInput => write!(f, "expected end of input"),
+            Self::UnexpectedEndOfInput => write!(f, "unexpected end of input"),
+            Self::UnclosedDelimiter { opener } => write!(f, "unclosed delimiter '{opener}'"),
+            Self::MismatchedDelimiter { opener: _, closer } => {
+                write!(f, "mismatched closing delimiter '{closer}'")
+            }
+            Self::EmptyRecord => write!(f, "record parser did not consume any input"),
+            Self::UnexpectedMatch => write!(f, "parser should not have matched"),
+            Self::NotEnoughRepetitions { min, found } => {
+                write!(f, "expected at least {min} repetitions, found {found}")
+            }
+            Self::FailedAfterSeparator => {
+                write!(f, "repeated value parser failed after separator")
+            }
+            Self::NoChoiceMatched => write!(f, "none of choices matched"),
+            Self::ValidationFailed => write!(f, "validator failed"),
+            Self::MappingFailed => write!(f, "an error was returned"),
+        }
+    }
+}
+
+/// Message of a secondary label or of a suggestion
+///
+/// Built-in messages are only formatted when displayed, so they can be customized
+/// with a [`MessageFormatter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Message provided as is
+    Text(Cow<'static, str>),
+
+    /// Suggest a candidate close to what was found (e.g. a keyword with a typo)
+    DidYouMean { candidate: Cow<'static, str> },
+
+    /// Label an opening delimiter that was never closed
+    UnclosedDelimiterLabel { opener: Box<str> },
+
+    /// Label an opening delimiter that was closed by a different delimiter
+    MismatchedDelimiterLabel { opener: Box<str>, closer: char },
+}
+
+impl Message {
+    /// Format the message, using the [`DefaultFormatter`]
+    pub fn format(&self) -> Cow<'_, str> {
+        self.format_with(&DefaultFormatter)
+    }
+
+    /// Format the message, using the provided formatter
+    ///
+    /// See [`Message::format`]
+    pub fn format_with(&self, formatter: &dyn MessageFormatter) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::DidYouMean { candidate } => Cow::Owned(formatter.did_you_mean(candidate)),
+            Self::UnclosedDelimiterLabel { opener } => {
+                Cow::Owned(formatter.unclosed_delimiter_label(opener))
+            }
+            Self::MismatchedDelimiterLabel { opener, closer } => {
+                Cow::Owned(formatter.mismatched_delimiter_label(opener, *closer))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format())
+    }
+}
+
+impl From<&'static str> for Message {
+    fn from(text: &'static str) -> Self {
+        Self::Text(Cow::Borrowed(text))
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self::Text(Cow::Owned(text))
+    }
+}
+
+impl From<Cow<'static, str>> for Message {
+    fn from(text: Cow<'static, str>) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// Formatter for all the messages produced by the library
+///
+/// Every method has a default implementation producing English messages,
+/// so implementors only need to override the ones they want to reword or translate.
+///
+/// Messages are formatted with the [`DefaultFormatter`] by [`crate::ParsingError::message`]
+/// and the [`fmt::Display`] implementations. Another formatter can be provided with
+/// [`crate::ParsingError::message_with`], or in the options of an error report.
+pub trait MessageFormatter {
+    /// Format a built-in error
+    fn kind(&self, kind: &ErrorKind) -> String {
+        kind.to_string()
+    }
+
+    /// Format what a parser expected
+    fn expectation(&self, expected: &ParserExpectation) -> String {
+        match expected {
+            ParserExpectation::Char(c) => format!("expected char '{c}'"),
+            ParserExpectation::Str(str) => format!("expected '{str}'"),
+            ParserExpectation::Byte(byte) => format!("expected byte 0x{byte:02X}"),
+            ParserExpectation::Bytes(bytes) => format!("expected b\"{}\"", bytes.escape_ascii()),
+            ParserExpectation::Custom(message) => message.to_string(),
+            ParserExpectation::Dynamic(message) => message.to_string(),
+            ParserExpectation::Kind(kind) => self.kind(kind),
+            ParserExpectation::OneOf(alternatives) => format!(
+                "expected one of {}",
+                alternatives
+                    .iter()
+                    .map(|alternative| self.alternative(alternative))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParserExpectation::Break => "got break (should not be possible)".to_owned(),
+        }
+    }
+
+    /// Format one of the alternatives of a [`ParserExpectation::OneOf`]
+    fn alternative(&self, expected: &ParserExpectation) -> String {
+        match expected {
+            ParserExpectation::Char(c) => format!("'{c}'"),
+            ParserExpectation::Str(str) => format!("'{str}'"),
+            ParserExpectation::Byte(byte) => format!("0x{byte:02X}"),
+            ParserExpectation::Bytes(bytes) => format!("b\"{}\"", bytes.escape_ascii()),
+            expected => self.expectation(expected),
+        }
+    }
+
+    /// Format what a parser found instead of what it expected
+    fn found(&self, found: &Found) -> String {
+        found.to_string()
+    }
+
+    /// Combine the formatted expectation with what was found instead
+    fn expected_found(&self, expected: &str, found: &str) -> String {
+        format!("{expected}, found {found}")
+    }
+
+    /// Format a frame of an error's context
+    ///
+    /// The line number (starting at 1) is only provided if the input is available
+    fn context_frame(&self, frame: &ContextFrame, line: Option<usize>) -> String {
+        match line {
+            Some(line) => format!("while parsing {} (line {line})", frame.label),
+            None => format!("while parsing {} (offset {})", frame.label, frame.at.offset),
+        }
+    }
+
+    /// Suggest a candidate close to what was found (e.g. a keyword with a typo)
+    fn did_you_mean(&self, candidate: &str) -> String {
+        format!("did you mean '{candidate}'?")
+    }
+
+    /// Label an opening delimiter that was never closed
+    fn unclosed_delimiter_label(&self, opener: &str) -> String {
+        format!("unclosed '{opener}' opened here")
+    }
+
+    /// Label an opening delimiter that was closed by a different delimiter
+    fn mismatched_delimiter_label(&self, opener: &str, closer: char) -> String {
+        format!("'{opener}' opened here does not match '{closer}'")
+    }
+
+    /// Format an error that caused a parsing error
+    fn caused_by(&self, cause: &str) -> String {
+        format!("caused by: {cause}")
+    }
+
+    /// Format the title of a report
+    fn report_title(&self, severity: Severity) -> String {
+        match severity {
+            Severity::Error => "Parsing failed",
+            Severity::Warning => "Parsing warning",
+            Severity::Note => "Parsing note",
+        }
+        .to_owned()
+    }
+}
+
+/// The default, English [`MessageFormatter`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFormatter;
+
+impl MessageFormatter for DefaultFormatter {}
+
+impl fmt::Debug for dyn MessageFormatter + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageFormatter").finish_non_exhaustive()
+    }
+}
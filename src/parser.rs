@@ -1,26 +1,28 @@
-use std::{borrow::Cow, ops::Deref, sync::LazyLock};
+use std::{borrow::Cow, error::Error, fmt, io::Read, ops::Deref, sync::LazyLock};
 
 use crate::{
-    Container, FileId, NoAllocContainer, ParserInput, ParserResult, ParsingError, parsers::*,
+    BinaryInput, ByteSource, Container, Diagnostic, FileId, InputKind, InputOf, NoAllocContainer,
+    ParseStream, ParserInput, ParserResult, ParsingError, ParsingSession, Span, TextInput,
+    TokenInput, UserState, parsers::*,
 };
 
 /// A parser takes an input and tries to consume the upcoming character(s) and transform it
 /// into a value.
 ///
+/// Parsers are generic over the kind of input they work on (see [`InputKind`]),
+/// which defaults to textual inputs.
+///
 /// Implement this trait will also perform auto-implementation for the [`ParserConstUtils`] and
 /// [`ParserNonConstUtils`] traits.
-pub trait Parser<T> {
+pub trait Parser<T, I: InputKind = TextInput> {
     /// Inner parsing function, to implement
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T>;
-}
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T>;
 
-/// Non-constant-function utilities for parsers
-pub trait ParserNonConstUtils<T>: Parser<T> {
     /// Parse an input with the current parser
     ///
     /// The input position will advance if the parsing is successful,
     /// and will not advance if the parsing fails
-    fn parse(&self, input: &mut ParserInput) -> ParserResult<T> {
+    fn parse(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         // "Clone" (copy) 'input'
         let mut input_copy = *input;
 
@@ -28,21 +30,200 @@ pub trait ParserNonConstUtils<T>: Parser<T> {
 
         // Only apply changes to input (cursor advance) if the parsing was successful
         // Otherwise, keep the original intact (this is equivalent to rollbacking in case of error)
-        result.inspect(|span| input.advance(span.at))
+        result
+            .inspect(|span| input.advance(span.at))
+            .map_err(|mut err| {
+                // Only record the failure where it happens, not at each nesting level
+                if !err.mark_recorded() {
+                    input.record_failure(&err);
+                }
+
+                err
+            })
     }
+}
 
+/// Non-constant-function utilities for parsers
+///
+/// Each entry point is available to the parsers supporting the related input kind
+pub trait ParserNonConstUtils<T> {
     /// Parse a string
     ///
     /// Will use [`FileId::None`] as the source
-    fn parse_str(&self, str: &str) -> ParserResult<T> {
+    fn parse_str(&self, str: &str) -> ParserResult<T>
+    where
+        Self: Parser<T>,
+    {
         self.parse_str_with_file_id(str, FileId::None)
     }
 
     /// Parse a string as a file
     ///
     /// Will use the provided file ID
-    fn parse_str_with_file_id(&self, str: &str, file_id: FileId) -> ParserResult<T> {
-        self.parse(&mut ParserInput::new(str, file_id))
+    ///
+    /// In case of non-critical failure, the error that happened the furthest in the input
+    /// is returned, even if it was discarded by a parser like [`ParserConstUtils::or_not`].
+    /// See [`ParsingSession`] for more details.
+    fn parse_str_with_file_id(&self, str: &str, file_id: FileId) -> ParserResult<T>
+    where
+        Self: Parser<T>,
+    {
+        let session = ParsingSession::new();
+
+        self.parse(&mut ParserInput::new(str, file_id, &session))
+            .map_err(|err| session.deepest_error(err))
+    }
+
+    /// Parse a slice of bytes
+    ///
+    /// Will use [`FileId::None`] as the source
+    fn parse_bytes(&self, bytes: &[u8]) -> ParserResult<T>
+    where
+        Self: Parser<T, BinaryInput>,
+    {
+        self.parse_bytes_with_file_id(bytes, FileId::None)
+    }
+
+    /// Parse a slice of bytes as a file
+    ///
+    /// Will use the provided file ID
+    ///
+    /// Textual parsers can still be used, and will only match valid UTF-8 content.
+    fn parse_bytes_with_file_id(&self, bytes: &[u8], file_id: FileId) -> ParserResult<T>
+    where
+        Self: Parser<T, BinaryInput>,
+    {
+        let session = ParsingSession::new();
+
+        self.parse(&mut ParserInput::new_bytes(bytes, file_id, &session))
+            .map_err(|err| session.deepest_error(err))
+    }
+
+    /// Parse tokens produced by a lexer
+    ///
+    /// The returned span and error locations are converted to the range the tokens cover
+    /// in the source (see [`ParserInput::source_range`])
+    fn parse_tokens<K: fmt::Debug>(&self, tokens: &[Span<K>]) -> ParserResult<T>
+    where
+        Self: Parser<T, TokenInput<K>>,
+    {
+        let session = ParsingSession::new();
+        let mut input = ParserInput::new_tokens(tokens, &session);
+
+        self.parse(&mut input)
+            .map(|span| Span::ate(input.source_range(span.at), span.data))
+            .map_err(|err| {
+                session
+                    .deepest_error(err)
+                    .map_ranges(|at| input.source_range(at))
+            })
+    }
+
+    /// Parse a stream record by record, without loading it entirely in memory
+    ///
+    /// The current parser is used to parse each record, and must consume at least one byte.
+    ///
+    /// Will use [`FileId::None`] as the source
+    ///
+    /// See [`ParseStream`]
+    fn parse_stream<R: Read>(&self, reader: R) -> ParseStream<'_, T, Self, R>
+    where
+        Self: Parser<T, BinaryInput> + Sized,
+    {
+        self.parse_stream_with_file_id(reader, FileId::None)
+    }
+
+    /// Parse a stream record by record, as a file
+    ///
+    /// See [`ParserNonConstUtils::parse_stream`]
+    fn parse_stream_with_file_id<R: Read>(
+        &self,
+        reader: R,
+        file_id: FileId,
+    ) -> ParseStream<'_, T, Self, R>
+    where
+        Self: Parser<T, BinaryInput> + Sized,
+    {
+        ParseStream::new(self, reader, file_id)
+    }
+
+    /// Parse a string with a context, which parsers can fetch
+    ///
+    /// See [`ParserInput::with_context`]
+    fn parse_str_with_context<C: Copy>(&self, str: &str, ctx: C) -> ParserResult<T>
+    where
+        Self: Parser<T, InputOf<str, C>>,
+    {
+        let session = ParsingSession::new();
+
+        self.parse(&mut ParserInput::new_with_ctx(
+            str,
+            FileId::None,
+            &session,
+            ctx,
+        ))
+        .map_err(|err| session.deepest_error(err))
+    }
+
+    /// Parse a string with a user state, which parsers can read and update
+    ///
+    /// Updates made by parsers that end up failing are undone.
+    /// The state is returned along with the result.
+    ///
+    /// See [`ParsingSession::with_state`]
+    fn parse_str_with_state<S: UserState>(&self, str: &str, state: S) -> (ParserResult<T>, S)
+    where
+        Self: Parser<T, InputOf<str, (), S>>,
+    {
+        let session = ParsingSession::new().with_state(state);
+
+        let result = ParserInput::new(str, FileId::None, &session)
+            .rollback_on_failure(|input| self.parse(input))
+            .map_err(|err| session.deepest_error(err));
+
+        (result, session.into_state())
+    }
+
+    /// Parse a string and collect the diagnostics emitted by the parsers
+    ///
+    /// See [`ParserInput::emit_diagnostic`]
+    fn parse_str_with_diagnostics(&self, str: &str) -> (ParserResult<T>, Vec<Diagnostic>)
+    where
+        Self: Parser<T>,
+    {
+        let session = ParsingSession::new();
+
+        let result = ParserInput::new(str, FileId::None, &session)
+            .rollback_on_failure(|input| self.parse(input))
+            .map_err(|err| session.deepest_error(err));
+
+        (result, session.take_diagnostics())
+    }
+
+    /// Parse a string, recovering from critical errors when possible
+    ///
+    /// Returns the parsed value if the parsing succeeded, along with all the errors
+    /// that were recovered from (see [`ParserConstUtils::recover_with`]).
+    ///
+    /// If the parsing failed, the final error is added at the end of the list.
+    fn parse_str_recovering(&self, str: &str) -> (Option<T>, Vec<ParsingError>)
+    where
+        Self: Parser<T>,
+    {
+        let session = ParsingSession::new().with_recovery();
+
+        let result = ParserInput::new(str, FileId::None, &session)
+            .rollback_on_failure(|input| self.parse(input));
+
+        let mut errors = session.take_recovered_errors();
+
+        match result {
+            Ok(span) => (Some(span.data), errors),
+            Err(err) => {
+                errors.push(session.deepest_error(err));
+                (None, errors)
+            }
+        }
     }
 
     /// "Erase" the parser's type
@@ -58,9 +239,9 @@ pub trait ParserNonConstUtils<T>: Parser<T> {
     /// static PARSER_1: LazilyDefined<&'static str> = lazily_define(|| Box::new(just("yeah")));
     /// static PARSER_2: LazilyDefined<&'static str> = lazily_define(|| just("yeah").erase_type());
     /// ```
-    fn erase_type(self) -> Box<dyn Parser<T> + Send + Sync>
+    fn erase_type<I: InputKind>(self) -> Box<dyn Parser<T, I> + Send + Sync>
     where
-        Self: Sized + Send + Sync + 'static,
+        Self: Parser<T, I> + Sized + Send + Sync + 'static,
     {
         Box::new(self)
     }
@@ -69,9 +250,9 @@ pub trait ParserNonConstUtils<T>: Parser<T> {
 /// Constant function utilities for parsers
 ///
 /// These can be evaluated at build time
-pub const trait ParserConstUtils<T>: Parser<T> {
+pub const trait ParserConstUtils<T, I: InputKind = TextInput>: Parser<T, I> {
     /// Chain this parser with another, getting both parsers' results combined
-    fn then<U, P: Parser<U>>(self, other: P) -> Then<T, Self, U, P>
+    fn then<U, P: Parser<U, I>>(self, other: P) -> Then<T, Self, U, P, I>
     where
         Self: Sized,
     {
@@ -79,7 +260,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Chain this parser with another but discard the latter's parsed value
-    fn then_ignore<U, P: Parser<U>>(self, other: P) -> ThenIgnore<T, Self, U, P>
+    fn then_ignore<U, P: Parser<U, I>>(self, other: P) -> ThenIgnore<T, Self, U, P, I>
     where
         Self: Sized,
     {
@@ -87,7 +268,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Chain this parser with another but discard the former's parsed value
-    fn ignore_then<U, P: Parser<U>>(self, other: P) -> IgnoreThen<T, Self, U, P>
+    fn ignore_then<U, P: Parser<U, I>>(self, other: P) -> IgnoreThen<T, Self, U, P, I>
     where
         Self: Sized,
     {
@@ -97,7 +278,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Only match if this parser succeeds and the provided parser succeeds as well
     ///
     /// The second parser will not make the input's position advance
-    fn followed_by<U, P: Parser<U>>(self, other: P) -> FollowedBy<T, Self, U, P>
+    fn followed_by<U, P: Parser<U, I>>(self, other: P) -> FollowedBy<T, Self, U, P, I>
     where
         Self: Sized,
     {
@@ -105,7 +286,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Only match if this parser succeeds and the provided parser doesn't
-    fn not_followed_by<U, P: Parser<U>>(self, other: P) -> NotFollowedBy<T, Self, U, P>
+    fn not_followed_by<U, P: Parser<U, I>>(self, other: P) -> NotFollowedBy<T, Self, U, P, I>
     where
         Self: Sized,
     {
@@ -115,7 +296,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Parse as many times as possible, until the parser eventually fails
     ///
     /// This will not allocate. To get the results directly in a [`Vec`], see [`ParserConstUtils::repeated_into_vec`]
-    fn repeated(self) -> Repeated<T, Self, NoAllocContainer>
+    fn repeated(self) -> Repeated<T, Self, NoAllocContainer, I>
     where
         Self: Sized,
     {
@@ -126,7 +307,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// All the parsed values will be put in a [`Vec`].
     /// To use another container, see [`ParserConstUtils::repeated_into_container`]
-    fn repeated_into_vec(self) -> Repeated<T, Self, Vec<T>>
+    fn repeated_into_vec(self) -> Repeated<T, Self, Vec<T>, I>
     where
         Self: Sized,
     {
@@ -137,7 +318,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// All the parsed values will be forwarded to the provided [`Container`] type.
     /// The container will then be returned.
-    fn repeated_into_container<C: Container<T>>(self) -> Repeated<T, Self, C>
+    fn repeated_into_container<C: Container<T>>(self) -> Repeated<T, Self, C, I>
     where
         Self: Sized,
     {
@@ -148,7 +329,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// * In case of success, the parser will succeed and return the parsed value wrapped in a [`Some`]
     /// * In case of failure, the parser will succeed and return a [`None`]
-    fn or_not(self) -> OrNot<T, Self>
+    fn or_not(self) -> OrNot<T, Self, I>
     where
         Self: Sized,
     {
@@ -158,7 +339,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Map the parsed value using a function
     ///
     /// Aking to [`Option::map`]
-    fn map<U, F: Fn(T) -> U>(self, mapper: F) -> Map<T, Self, U, F>
+    fn map<U, F: Fn(T) -> U>(self, mapper: F) -> Map<T, Self, U, F, I>
     where
         Self: Sized,
     {
@@ -166,7 +347,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Get the input string matched by the parser and map it using a function
-    fn map_consumed_str<U, F: Fn(&str) -> U>(self, mapper: F) -> MapConsumedStr<T, Self, U, F>
+    fn map_consumed_str<U, F: Fn(&str) -> U>(self, mapper: F) -> MapConsumedStr<T, Self, U, F, I>
     where
         Self: Sized,
     {
@@ -176,7 +357,10 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Transform and validate the parsed value using the provided function
     ///
     /// If you only want to return a critical error message, see [`ParserConstUtils::and_then_or_critical`]
-    fn and_then<U, F: Fn(T) -> Result<U, ParsingError>>(self, mapper: F) -> AndThen<T, Self, U, F>
+    fn and_then<U, F: Fn(T) -> Result<U, ParsingError>>(
+        self,
+        mapper: F,
+    ) -> AndThen<T, Self, U, F, I>
     where
         Self: Sized,
     {
@@ -188,15 +372,28 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     fn and_then_or_critical<U, F: Fn(T) -> Result<U, Cow<'static, str>>>(
         self,
         mapper: F,
-    ) -> AndThenOrCritical<T, Self, U, F>
+    ) -> AndThenOrCritical<T, Self, U, F, I>
     where
         Self: Sized,
     {
         AndThenOrCritical::new(self, mapper)
     }
 
+    /// Transform and validate the parsed value using the provided function
+    /// Failures are [critical](`ParserConstUtils::critical`) and carry the returned error,
+    /// which can be retrieved with [`ParsingError::downcast_source`]
+    fn and_then_or_error<U, E: Error + Send + Sync + 'static, F: Fn(T) -> Result<U, E>>(
+        self,
+        mapper: F,
+    ) -> AndThenOrError<T, Self, U, E, F, I>
+    where
+        Self: Sized,
+    {
+        AndThenOrError::new(self, mapper)
+    }
+
     /// Wrap the parsed value in a [`Spanned`]
-    fn spanned(self) -> Spanned<T, Self>
+    fn spanned(self) -> Spanned<T, Self, I>
     where
         Self: Sized,
     {
@@ -204,7 +401,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Collect the parsed value using the provided iterator type
-    fn collect<C>(self) -> Map<T, Self, C, fn(T) -> C>
+    fn collect<C>(self) -> Map<T, Self, C, fn(T) -> C, I>
     where
         Self: Sized,
         T: IntoIterator,
@@ -214,7 +411,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Collect the input string matched by the parser
-    fn collect_string(self) -> CollectString<T, Self>
+    fn collect_string(self) -> CollectString<T, Self, I>
     where
         Self: Sized,
     {
@@ -225,37 +422,135 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// Atomic errors are the smallest possible error types,
     /// every error nested below their level is discarded
-    fn atomic_err(self, message: &'static str) -> AtomicErr<T, Self>
+    fn atomic_err<M: Into<Cow<'static, str>> + Clone>(self, message: M) -> AtomicErr<T, Self, I, M>
     where
         Self: Sized,
     {
         AtomicErr::new(self, message)
     }
 
+    /// Provide an atomic error with a code if the parser fails
+    ///
+    /// See [`ParserConstUtils::atomic_err`] and [`ParsingError::with_code`]
+    fn atomic_err_code<M: Into<Cow<'static, str>> + Clone>(
+        self,
+        code: &'static str,
+        message: M,
+    ) -> AtomicErr<T, Self, I, M>
+    where
+        Self: Sized,
+    {
+        AtomicErr::new(self, message).with_code(code)
+    }
+
+    /// Label the parser
+    ///
+    /// If the parser fails, the error's context will contain a frame with the provided label
+    /// and the location the parser started at (see [`ParsingError::context`]).
+    fn labelled<L: Into<Cow<'static, str>> + Clone>(self, label: L) -> Labelled<T, Self, I, L>
+    where
+        Self: Sized,
+    {
+        Labelled::new(self, label)
+    }
+
     /// Mark the parser as critical
     ///
     /// In case of failure, the whole chain of parsing will fail with the provided message
-    fn critical(self, message: &'static str) -> Critical<T, Self>
+    fn critical<M: Into<Cow<'static, str>> + Clone>(self, message: M) -> Critical<T, Self, I, M>
     where
         Self: Sized,
     {
         Critical::new(self, Some(message))
     }
 
+    /// Mark the parser as critical, with an error code
+    ///
+    /// See [`ParserConstUtils::critical`] and [`ParsingError::with_code`]
+    fn critical_with_code<M: Into<Cow<'static, str>> + Clone>(
+        self,
+        code: &'static str,
+        message: M,
+    ) -> Critical<T, Self, I, M>
+    where
+        Self: Sized,
+    {
+        Critical::new(self, Some(message)).with_code(code)
+    }
+
     /// Mark the parser as critical
     ///
     /// In case of failure, the whole chain of parsing will fail with a default message
-    fn critical_auto_msg(self) -> Critical<T, Self>
+    fn critical_auto_msg(self) -> Critical<T, Self, I>
     where
         Self: Sized,
     {
         Critical::new(self, None)
     }
 
+    /// Recover from critical failures using the provided strategy
+    ///
+    /// When the parser fails with a critical error, the strategy is used to skip
+    /// the erroneous input and produce a placeholder value. The error is then recorded
+    /// in the parsing session (see [`ParserNonConstUtils::parse_str_recovering`]).
+    ///
+    /// If the input has no parsing session with recovery enabled attached,
+    /// or if the strategy fails, the original error is returned.
+    fn recover_with<S: RecoveryStrategy<T, I>>(self, strategy: S) -> RecoverWith<T, Self, S, I>
+    where
+        Self: Sized,
+    {
+        RecoverWith::new(self, strategy)
+    }
+
+    /// Recover from critical failures by skipping a block delimited by the provided characters
+    ///
+    /// The parser must have started at the opening delimiter. Nested blocks are skipped as well.
+    /// The placeholder value is the type's default one.
+    ///
+    /// See [`ParserConstUtils::recover_with`]
+    fn recover_nested(self, open: char, close: char) -> RecoverWith<T, Self, NestedDelimiters, I>
+    where
+        Self: Sized,
+        T: Default,
+        I::Source: ByteSource,
+    {
+        RecoverWith::new(self, NestedDelimiters::new(open, close))
+    }
+
+    /// Recover from critical failures by returning the provided value
+    ///
+    /// The input is skipped up to the end of the error's location.
+    /// Fails to recover if that location ends where the parser started.
+    ///
+    /// See [`ParserConstUtils::recover_with`]
+    fn recover_with_fallback(self, value: T) -> RecoverWith<T, Self, Fallback<T>, I>
+    where
+        Self: Sized,
+        T: Clone,
+    {
+        RecoverWith::new(self, Fallback::new(value))
+    }
+
+    /// Emit a warning if the parsed value matches the provided predicate
+    ///
+    /// The warning covers the input consumed by the parser.
+    /// See [`ParserNonConstUtils::parse_str_with_diagnostics`]
+    fn warn_if<F: Fn(&T) -> bool, M: Into<Cow<'static, str>> + Clone>(
+        self,
+        predicate: F,
+        message: M,
+    ) -> WarnIf<T, Self, F, I, M>
+    where
+        Self: Sized,
+    {
+        WarnIf::new(self, predicate, message)
+    }
+
     /// Make the parser silent
     ///
     /// The parsed value will be `()`. Akin to using `.map(|_| ())` on the parser.
-    fn silenced(self) -> Silenced<T, Self>
+    fn silenced(self) -> Silenced<T, Self, I>
     where
         Self: Sized,
     {
@@ -265,7 +560,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Require the parser to be preceded by and followed by the provided padding
     ///
     /// The padding parser's values are discarded
-    fn padded_by<P, PP: Parser<P>>(self, padding: PP) -> PaddedBy<T, Self, P, PP>
+    fn padded_by<P, PP: Parser<P, I>>(self, padding: PP) -> PaddedBy<T, Self, P, PP, I>
     where
         Self: Sized,
     {
@@ -275,11 +570,13 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Require the parser to be preceded by and followed by the provided parsers
     ///
     /// The parsers' values are discarded
-    fn surrounded_by<L, LP: Parser<L>, R, RP: Parser<R>>(
+    ///
+    /// To report unclosed delimiters, see [`SurroundedBy::delimiter_aware`]
+    fn surrounded_by<L, LP: Parser<L, I>, R, RP: Parser<R, I>>(
         self,
         left: LP,
         right: RP,
-    ) -> SurroundedBy<L, LP, T, Self, R, RP>
+    ) -> SurroundedBy<L, LP, T, Self, R, RP, I>
     where
         Self: Sized,
     {
@@ -289,7 +586,10 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Repeat the parser with the required provided separator between each repetition
     ///
     /// If you want to collect the results, see [`ParserConstUtils::separated_by_into_vec`].
-    fn separated_by<S, P: Parser<S>>(self, sep: P) -> SeparatedBy<T, Self, S, P, NoAllocContainer>
+    fn separated_by<S, P: Parser<S, I>>(
+        self,
+        sep: P,
+    ) -> SeparatedBy<T, Self, S, P, NoAllocContainer, I>
     where
         Self: Sized,
     {
@@ -300,7 +600,10 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// All results are collected into a [`Vec`].
     /// To use a custom container, see [`ParserConstUtils::separated_by_into_container`]
-    fn separated_by_into_vec<S, P: Parser<S>>(self, sep: P) -> SeparatedBy<T, Self, S, P, Vec<T>>
+    fn separated_by_into_vec<S, P: Parser<S, I>>(
+        self,
+        sep: P,
+    ) -> SeparatedBy<T, Self, S, P, Vec<T>, I>
     where
         Self: Sized,
     {
@@ -310,10 +613,10 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Repeat the parser with the required provided separator between each repetition
     ///
     /// All results are forwarded to the provided [`Container`] type, which is then returned.
-    fn separated_by_into_container<C: Container<T>, S, P: Parser<S>>(
+    fn separated_by_into_container<C: Container<T>, S, P: Parser<S, I>>(
         self,
         sep: P,
-    ) -> SeparatedBy<T, Self, S, P, C>
+    ) -> SeparatedBy<T, Self, S, P, C, I>
     where
         Self: Sized,
     {
@@ -324,7 +627,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// Requires the parser to return a nested iterator.
     /// The values are discarded. To collect them, see [`ParserConstUtils::flatten_into_vec`]
-    fn flattened<U, S>(self) -> Flattened<U, S, T, Self, NoAllocContainer>
+    fn flattened<U, S>(self) -> Flattened<U, S, T, Self, NoAllocContainer, I>
     where
         Self: Sized,
         T: IntoIterator<Item = S>,
@@ -338,7 +641,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     ///
     /// The values are collected into a [`Vec`].
     /// To use a custom container, see [`ParserConstUtils::flatten_into_container`]
-    fn flatten_into_vec<U, S>(self) -> Flattened<U, S, T, Self, Vec<U>>
+    fn flatten_into_vec<U, S>(self) -> Flattened<U, S, T, Self, Vec<U>, I>
     where
         Self: Sized,
         T: IntoIterator<Item = S>,
@@ -351,7 +654,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Requires the parser to return a nested iterator.
     ///
     /// All results are forwarded to the provided [`Container`] type, which is then returned.
-    fn flatten_into_container<U, S, C: Container<U>>(self) -> Flattened<U, S, T, Self, C>
+    fn flatten_into_container<U, S, C: Container<U>>(self) -> Flattened<U, S, T, Self, C, I>
     where
         Self: Sized,
         T: IntoIterator<Item = S>,
@@ -361,7 +664,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Discard the parsed value and replace it with a fixed value
-    fn to<U: Copy>(self, data: U) -> To<T, Self, U>
+    fn to<U: Copy>(self, data: U) -> To<T, Self, U, I>
     where
         Self: Sized,
     {
@@ -369,7 +672,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     }
 
     /// Require the parser to match the entire input
-    fn full(self) -> Full<T, Self>
+    fn full(self) -> Full<T, Self, I>
     where
         Self: Sized,
     {
@@ -379,15 +682,15 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Allow the parser to fallback to another parser in case of failure
     ///
     /// If you have multiple choices, see [`choice`](`crate::parsers::helpers::choice`)
-    fn or<P: Parser<T>>(self, other: P) -> Choice<(Self, P), T>
+    fn or<P: Parser<T, I>>(self, other: P) -> Choice<(Self, P), T, I>
     where
         Self: Sized,
     {
-        Choice::<(Self, P), T>::new((self, other))
+        Choice::<(Self, P), T, I>::new((self, other))
     }
 
     /// Validate the parsed value with a predicate
-    fn validate<F: Fn(&T) -> bool>(self, validator: F) -> Validate<T, Self, F>
+    fn validate<F: Fn(&T) -> bool>(self, validator: F) -> Validate<T, Self, F, I>
     where
         Self: Sized,
     {
@@ -397,11 +700,11 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// Validate the parsed value with a predicate or return a provided critical error message
     ///
     /// If you want to customize the validation message, use [`ParserConstUtils::validate_or_dynamic_critical`]
-    fn validate_or_critical<F: Fn(&T) -> bool>(
+    fn validate_or_critical<F: Fn(&T) -> bool, M: Into<Cow<'static, str>> + Clone>(
         self,
         validator: F,
-        message: &'static str,
-    ) -> ValidateOrCriticalMsg<T, Self, F>
+        message: M,
+    ) -> ValidateOrCriticalMsg<T, Self, F, I, M>
     where
         Self: Sized,
     {
@@ -414,15 +717,81 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     fn validate_or_dynamic_critical<F: Fn(&T) -> Result<(), Cow<'static, str>>>(
         self,
         validator: F,
-    ) -> ValidateOrDynamicCriticalMsg<T, Self, F>
+    ) -> ValidateOrDynamicCriticalMsg<T, Self, F, I>
     where
         Self: Sized,
     {
         ValidateOrDynamicCriticalMsg::new(self, validator)
     }
 
+    /// Parse with the provided context instead of the input's one
+    ///
+    /// The input's context is restored afterwards, e.g. to parse a nested region in a different mode.
+    /// It may have a different type than the provided one.
+    ///
+    /// See [`ParserInput::with_context`]
+    fn with_context<C: Copy, O: InputKind<WithContext<C> = I>>(
+        self,
+        ctx: C,
+    ) -> WithContext<T, Self, C, O>
+    where
+        Self: Sized,
+    {
+        WithContext::new(self, ctx)
+    }
+
+    /// Parse with a context derived from the input's one
+    ///
+    /// The input's context is restored afterwards.
+    fn map_context<D: Copy, O: InputKind<WithContext<D> = I>, F: Fn(O::Context) -> D>(
+        self,
+        mapper: F,
+    ) -> MapContext<T, Self, D, F, O>
+    where
+        Self: Sized,
+    {
+        MapContext::new(self, mapper)
+    }
+
+    /// Validate the parsed value with a predicate, which can read the user state
+    ///
+    /// The state's type is part of the input's [`InputKind`] (see [`ParsingSession::with_state`]).
+    fn validate_with_state<F: Fn(&T, &I::State) -> bool>(
+        self,
+        validator: F,
+    ) -> ValidateWithState<T, Self, F, I>
+    where
+        Self: Sized,
+    {
+        ValidateWithState::new(self, validator)
+    }
+
+    /// Update the user state using the parsed value
+    ///
+    /// The update is undone if a parser containing this one fails afterwards.
+    ///
+    /// The state's type is part of the input's [`InputKind`] (see [`ParsingSession::with_state`]),
+    /// so the parser can only be used with a state of the expected type:
+    ///
+    /// ```compile_fail
+    /// use parsy::{ParserConstUtils, ParserNonConstUtils, parsers::helpers::char};
+    ///
+    /// char('a')
+    ///     .update_state(|_, chars: &mut Vec<char>| chars.push('a'))
+    ///     .parse_str("a");
+    /// ```
+    fn update_state<F: Fn(&T, &mut I::State)>(self, updater: F) -> UpdateState<T, Self, F, I>
+    where
+        Self: Sized,
+    {
+        UpdateState::new(self, updater)
+    }
+
     /// Debug the input and output values of the parser using the provided debugger
-    fn debug<F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)>(self, debugger: F) -> Debugging<T, Self, F>
+    fn debug<F: for<'a, 'b> Fn(DebugType<'a, 'b, T, I>)>(
+        self,
+        debugger: F,
+    ) -> Debugging<T, Self, F, I>
     where
         Self: Sized,
     {
@@ -441,7 +810,7 @@ pub const trait ParserConstUtils<T>: Parser<T> {
     /// static A: LazilyDefined<()> = lazily_define(|| Box::new(B.static_ref().to(())));
     /// static B: LazilyDefined<()> = lazily_define(|| Box::new(A.static_ref().to(())));
     /// ```
-    fn static_ref(&'static self) -> StaticRef<T, Self>
+    fn static_ref(&'static self) -> StaticRef<T, Self, I>
     where
         Self: Sized,
     {
@@ -450,20 +819,21 @@ pub const trait ParserConstUtils<T>: Parser<T> {
 }
 
 // Add cosnt utilities to all parsers
-impl<T, P: Parser<T>> const ParserConstUtils<T> for P {}
+impl<T, I: InputKind, P: Parser<T, I>> const ParserConstUtils<T, I> for P {}
 
 // Add non-const utilities to all parsers
-impl<T, P: Parser<T>> ParserNonConstUtils<T> for P {}
+// (each one being only available for the parsers supporting the related input kind)
+impl<T, P: ?Sized> ParserNonConstUtils<T> for P {}
 
 // Implement for
-impl<T, P: Parser<T> + ?Sized> Parser<T> for Box<P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, I: InputKind, P: Parser<T, I> + ?Sized> Parser<T, I> for Box<P> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.deref().parse_inner(input)
     }
 }
 
-impl<T, P: Parser<T>> Parser<T> for LazyLock<P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, I: InputKind, P: Parser<T, I>> Parser<T, I> for LazyLock<P> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.deref().parse_inner(input)
     }
 }
@@ -0,0 +1,39 @@
+use crate::{ByteSource, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError};
+
+/// See [`byte`](`crate::parsers::helpers::byte`)
+#[derive(Clone, Copy)]
+pub struct Byte {
+    byte: u8,
+}
+
+impl Byte {
+    pub const fn new(byte: u8) -> Self {
+        Self { byte }
+    }
+}
+
+impl<I: InputKind<Source: ByteSource>> Parser<u8, I> for Byte {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<u8> {
+        let start = input.at();
+
+        let remaining = input.remaining_bytes();
+
+        let Some(&byte) = remaining.first() else {
+            return Err(ParsingError::expected_byte(start.range(0), self.byte)
+                .with_found(Found::EndOfInput));
+        };
+
+        if byte != self.byte {
+            return Err(ParsingError::expected_byte(start.range(1), self.byte)
+                .with_found(Found::Byte(byte)));
+        }
+
+        // Textual inputs can't be cut inside a character
+        let span = input.try_eat_bytes(1).ok_or_else(|| {
+            ParsingError::expected_byte(start.range(1), self.byte)
+                .with_found(Found::Byte(remaining[1]))
+        })?;
+
+        Ok(span.forge_here(self.byte))
+    }
+}
@@ -0,0 +1,39 @@
+use perfect_derive::perfect_derive;
+
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
+
+/// See [`filter_byte`](`crate::parsers::helpers::filter_byte`)
+#[perfect_derive(Clone, Copy)]
+pub struct FilterByte<F: Fn(u8) -> bool = fn(u8) -> bool> {
+    func: F,
+}
+
+impl<F: Fn(u8) -> bool> FilterByte<F> {
+    pub const fn new(func: F) -> Self {
+        Self { func }
+    }
+}
+
+impl<F: Fn(u8) -> bool, I: InputKind<Source: ByteSource>> Parser<u8, I> for FilterByte<F> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<u8> {
+        let start = input.at();
+
+        let span = input.try_eat_bytes(1).ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::ByteFilter)
+                .with_found(Found::next_byte_at(input))
+        })?;
+
+        let byte = span.data[0];
+
+        if (self.func)(byte) {
+            Ok(span.forge_here(byte))
+        } else {
+            Err(
+                ParsingError::from_kind(start.range(1), ErrorKind::ByteFilter)
+                    .with_found(Found::Byte(byte)),
+            )
+        }
+    }
+}
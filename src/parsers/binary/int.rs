@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
+
+/// Byte order of a fixed-width integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Most significant byte first
+    Big,
+
+    /// Least significant byte first
+    Little,
+}
+
+/// Fixed-width integer that can be decoded from bytes
+pub trait BinaryInt: Sized {
+    /// Number of bytes the integer is encoded on
+    const SIZE: usize;
+
+    /// Decode the integer from exactly [`BinaryInt::SIZE`] bytes
+    fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self;
+}
+
+macro_rules! impl_binary_int {
+    ($($int: ty),*) => {
+        $(
+            impl BinaryInt for $int {
+                const SIZE: usize = size_of::<$int>();
+
+                fn from_bytes(bytes: &[u8], endianness: Endianness) -> Self {
+                    let bytes = bytes.try_into().unwrap();
+
+                    match endianness {
+                        Endianness::Big => <$int>::from_be_bytes(bytes),
+                        Endianness::Little => <$int>::from_le_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// See [`int_be`](`crate::parsers::helpers::int_be`)
+/// and [`int_le`](`crate::parsers::helpers::int_le`)
+#[perfect_derive(Clone, Copy)]
+pub struct Int<N: BinaryInt> {
+    endianness: Endianness,
+    _p: PhantomData<N>,
+}
+
+impl<N: BinaryInt> Int<N> {
+    pub const fn new(endianness: Endianness) -> Self {
+        Self {
+            endianness,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<N: BinaryInt, I: InputKind<Source: ByteSource>> Parser<N, I> for Int<N> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<N> {
+        let start = input.at();
+        let remaining = input.remaining_bytes();
+
+        let span = input.try_eat_bytes(N::SIZE).ok_or_else(|| {
+            // Textual inputs can't be cut inside a character either
+            let found = match remaining.get(N::SIZE) {
+                Some(byte) => Found::Byte(*byte),
+                None => Found::EndOfInput,
+            };
+
+            ParsingError::from_kind(
+                start.range(remaining.len().min(N::SIZE)),
+                ErrorKind::Int { size: N::SIZE },
+            )
+            .with_found(found)
+        })?;
+
+        Ok(span.map(|bytes| N::from_bytes(bytes, self.endianness)))
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{ByteSource, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError};
+
+/// See [`just_bytes`](`crate::parsers::helpers::just_bytes`)
+#[derive(Clone, Copy)]
+pub struct JustBytes {
+    bytes: &'static [u8],
+}
+
+impl JustBytes {
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<I: InputKind<Source: ByteSource>> Parser<&'static [u8], I> for JustBytes {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<&'static [u8]> {
+        let start = input.at();
+        let remaining = input.remaining_bytes();
+
+        // Find the first byte that differs from the expected ones
+        let matching = remaining
+            .iter()
+            .zip(self.bytes)
+            .take_while(|(byte, expected)| byte == expected)
+            .count();
+
+        // Textual inputs can't be cut inside a character, in which case
+        // the mismatch is on the byte that continues it
+        if matching == self.bytes.len()
+            && let Some(span) = input.try_eat_bytes(matching)
+        {
+            // Replace success data with the stored bytes to get a 'static lifetime
+            return Ok(span.forge_here(self.bytes));
+        }
+
+        let found = match remaining.get(matching) {
+            Some(byte) => Found::Byte(*byte),
+            None => Found::EndOfInput,
+        };
+
+        Err(ParsingError::expected_bytes(start.range(matching), self.bytes).with_found(found))
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
+
+/// See [`uleb128`](`crate::parsers::helpers::uleb128`)
+#[derive(Clone, Copy)]
+pub struct UnsignedLeb128;
+
+/// See [`sleb128`](`crate::parsers::helpers::sleb128`)
+#[derive(Clone, Copy)]
+pub struct SignedLeb128;
+
+/// Eat the bytes of a LEB128-encoded integer, decoding its raw 64-bit payload
+///
+/// Returns the payload along with the number of meaningful bits in it
+fn eat_leb128<I: InputKind<Source: ByteSource>>(
+    input: &mut ParserInput<I>,
+    signed: bool,
+) -> ParserResult<(u64, u32)> {
+    let start = input.at();
+
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    let mut len = 0;
+
+    loop {
+        let Some(&byte) = input.remaining_bytes().get(len) else {
+            return Err(ParsingError::from_kind(start.range(len), ErrorKind::Leb128)
+                .with_found(Found::EndOfInput));
+        };
+
+        len += 1;
+
+        let payload = u64::from(byte & 0x7F);
+
+        // Bits that don't fit in the value must only extend it
+        let overflows = match shift {
+            64.. => true,
+            58..64 => {
+                let kept = 64 - shift;
+                let sign_extension = if signed && (payload >> (kept - 1)) & 1 == 1 {
+                    0x7F >> kept
+                } else {
+                    0
+                };
+
+                payload >> kept != sign_extension
+            }
+            _ => false,
+        };
+
+        if overflows {
+            return Err(ParsingError::from_kind(
+                start.range(len),
+                ErrorKind::Leb128Overflow,
+            ));
+        }
+
+        value |= payload << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    let span = input.try_eat_bytes(len).unwrap();
+
+    Ok(span.forge_here((value, shift.min(64))))
+}
+
+impl<I: InputKind<Source: ByteSource>> Parser<u64, I> for UnsignedLeb128 {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<u64> {
+        Ok(eat_leb128(input, false)?.map(|(value, _)| value))
+    }
+}
+
+impl<I: InputKind<Source: ByteSource>> Parser<i64, I> for SignedLeb128 {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<i64> {
+        Ok(eat_leb128(input, true)?.map(|(value, bits)| {
+            // Extend the sign bit of the last group
+            let unused = 64 - bits;
+            ((value << unused) as i64) >> unused
+        }))
+    }
+}
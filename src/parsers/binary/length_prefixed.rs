@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
+};
+
+/// See [`length_prefixed`](`crate::parsers::helpers::length_prefixed`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct LengthPrefixed<L: TryInto<usize>, LP: Parser<L, I>, T, P: Parser<T, I>, I: InputKind> {
+    length: LP,
+    content: P,
+    _p: PhantomData<(L, T, I)>,
+}
+
+impl<L: TryInto<usize>, LP: Parser<L, I>, T, P: Parser<T, I>, I: InputKind>
+    LengthPrefixed<L, LP, T, P, I>
+{
+    pub const fn new(length: LP, content: P) -> Self {
+        Self {
+            length,
+            content,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<L: TryInto<usize>, LP: Parser<L, I>, T, P: Parser<T, I>, I: InputKind<Source: ByteSource>>
+    Parser<T, I> for LengthPrefixed<L, LP, T, P, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let start = input.at();
+
+        let length = self.length.parse(input)?;
+
+        let len = length
+            .data
+            .try_into()
+            .map_err(|_| ParsingError::from_kind(length.at, ErrorKind::FieldLengthOverflow))?;
+
+        let field_start = input.at();
+        let remaining = input.remaining_bytes().len();
+
+        let mut field = input.truncated(len).ok_or_else(|| {
+            ParsingError::from_kind(
+                field_start.range(remaining),
+                ErrorKind::TruncatedField { len, remaining },
+            )
+            .with_found(Found::EndOfInput)
+        })?;
+
+        let content = self.content.parse(&mut field)?;
+
+        let unconsumed = field.remaining_bytes().len();
+
+        if unconsumed > 0 {
+            return Err(ParsingError::from_kind(
+                field.range(unconsumed),
+                ErrorKind::UnconsumedField {
+                    remaining: unconsumed,
+                },
+            )
+            .with_found(Found::next_at(&field)));
+        }
+
+        input.advance(field_start.range(len));
+
+        Ok(Span::ate(start.range(length.at.len + len), content.data))
+    }
+}
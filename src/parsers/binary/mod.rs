@@ -0,0 +1,17 @@
+mod byte;
+mod filter_byte;
+mod int;
+mod just_bytes;
+mod leb128;
+mod length_prefixed;
+mod take_bytes;
+
+pub use self::{
+    byte::Byte,
+    filter_byte::FilterByte,
+    int::{BinaryInt, Endianness, Int},
+    just_bytes::JustBytes,
+    leb128::{SignedLeb128, UnsignedLeb128},
+    length_prefixed::LengthPrefixed,
+    take_bytes::TakeBytes,
+};
@@ -0,0 +1,41 @@
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
+
+/// See [`take_bytes`](`crate::parsers::helpers::take_bytes`)
+/// and [`rest_bytes`](`crate::parsers::helpers::rest_bytes`)
+#[derive(Clone, Copy)]
+pub struct TakeBytes {
+    len: Option<usize>,
+}
+
+impl TakeBytes {
+    pub const fn new(len: Option<usize>) -> Self {
+        Self { len }
+    }
+}
+
+impl<I: InputKind<Source: ByteSource>> Parser<Vec<u8>, I> for TakeBytes {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<Vec<u8>> {
+        let start = input.at();
+        let remaining = input.remaining_bytes().len();
+
+        let len = self.len.unwrap_or(remaining);
+
+        let span = input.try_eat_bytes(len).ok_or_else(|| {
+            if len > remaining {
+                return ParsingError::from_kind(
+                    start.range(remaining),
+                    ErrorKind::TruncatedField { len, remaining },
+                )
+                .with_found(Found::EndOfInput);
+            }
+
+            // Textual inputs can't be cut inside a character
+            ParsingError::from_kind(start.range(len), ErrorKind::InvalidUtf8)
+                .with_found(Found::Byte(input.remaining_bytes()[len]))
+        })?;
+
+        Ok(span.map(<[u8]>::to_vec))
+    }
+}
@@ -2,17 +2,19 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
 
 /// See [`and_then`](`crate::ParserConstUtils::and_then`)
 #[perfect_derive(Clone, Copy)]
-pub struct AndThen<T, P: Parser<T>, U, F: Fn(T) -> Result<U, ParsingError>> {
+pub struct AndThen<T, P: Parser<T, I>, U, F: Fn(T) -> Result<U, ParsingError>, I: InputKind> {
     parser: P,
     mapper: F,
-    _p: PhantomData<(T, U)>,
+    _p: PhantomData<(T, U, I)>,
 }
 
-impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, ParsingError>> AndThen<T, P, U, F> {
+impl<T, P: Parser<T, I>, U, F: Fn(T) -> Result<U, ParsingError>, I: InputKind>
+    AndThen<T, P, U, F, I>
+{
     pub const fn new(parser: P, mapper: F) -> Self {
         Self {
             parser,
@@ -22,8 +24,10 @@ impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, ParsingError>> AndThen<T, P, U, F
     }
 }
 
-impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, ParsingError>> Parser<U> for AndThen<T, P, U, F> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<U> {
+impl<T, P: Parser<T, I>, U, F: Fn(T) -> Result<U, ParsingError>, I: InputKind> Parser<U, I>
+    for AndThen<T, P, U, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<U> {
         let Span { data, at } = self.parser.parse(input)?;
         (self.mapper)(data).map(|data| Span::ate(at, data))
     }
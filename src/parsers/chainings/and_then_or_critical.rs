@@ -2,17 +2,25 @@ use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError, Span};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
 
 /// See [`and_then_or_critical`](`crate::ParserConstUtils::and_then_or_critical`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct AndThenOrCritical<T, P: Parser<T>, U, F: Fn(T) -> Result<U, Cow<'static, str>>> {
+pub struct AndThenOrCritical<
+    T,
+    P: Parser<T, I>,
+    U,
+    F: Fn(T) -> Result<U, Cow<'static, str>>,
+    I: InputKind,
+> {
     parser: P,
     mapper: F,
-    _p: PhantomData<(T, U)>,
+    _p: PhantomData<(T, U, I)>,
 }
 
-impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, Cow<'static, str>>> AndThenOrCritical<T, P, U, F> {
+impl<T, P: Parser<T, I>, U, F: Fn(T) -> Result<U, Cow<'static, str>>, I: InputKind>
+    AndThenOrCritical<T, P, U, F, I>
+{
     pub const fn new(parser: P, mapper: F) -> Self {
         Self {
             parser,
@@ -22,14 +30,14 @@ impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, Cow<'static, str>>> AndThenOrCrit
     }
 }
 
-impl<T, P: Parser<T>, U, F: Fn(T) -> Result<U, Cow<'static, str>>> Parser<U>
-    for AndThenOrCritical<T, P, U, F>
+impl<T, P: Parser<T, I>, U, F: Fn(T) -> Result<U, Cow<'static, str>>, I: InputKind> Parser<U, I>
+    for AndThenOrCritical<T, P, U, F, I>
 {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<U> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<U> {
         let Span { data, at } = self.parser.parse(input)?;
 
         (self.mapper)(data)
             .map(|data| Span::ate(at, data))
-            .map_err(|err| ParsingError::custom(at, "an error was returned").criticalize(err))
+            .map_err(|err| ParsingError::from_kind(at, ErrorKind::MappingFailed).criticalize(err))
     }
 }
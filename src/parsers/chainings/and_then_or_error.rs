@@ -0,0 +1,59 @@
+use std::{error::Error, marker::PhantomData};
+
+use perfect_derive::perfect_derive;
+
+use crate::{InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
+
+/// See [`and_then_or_error`](`crate::ParserConstUtils::and_then_or_error`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct AndThenOrError<
+    T,
+    P: Parser<T, I>,
+    U,
+    E: Error + Send + Sync + 'static,
+    F: Fn(T) -> Result<U, E>,
+    I: InputKind,
+> {
+    parser: P,
+    mapper: F,
+    _p: PhantomData<(T, U, E, I)>,
+}
+
+impl<
+    T,
+    P: Parser<T, I>,
+    U,
+    E: Error + Send + Sync + 'static,
+    F: Fn(T) -> Result<U, E>,
+    I: InputKind,
+> AndThenOrError<T, P, U, E, F, I>
+{
+    pub const fn new(parser: P, mapper: F) -> Self {
+        Self {
+            parser,
+            mapper,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<
+    T,
+    P: Parser<T, I>,
+    U,
+    E: Error + Send + Sync + 'static,
+    F: Fn(T) -> Result<U, E>,
+    I: InputKind,
+> Parser<U, I> for AndThenOrError<T, P, U, E, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<U> {
+        let Span { data, at } = self.parser.parse(input)?;
+
+        (self.mapper)(data)
+            .map(|data| Span::ate(at, data))
+            .map_err(|err| {
+                let message = err.to_string();
+                ParsingError::from_source(at, err).criticalize(message)
+            })
+    }
+}
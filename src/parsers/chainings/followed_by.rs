@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`followed_by`](`crate::ParserConstUtils::followed_by`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct FollowedBy<F, FP: Parser<F>, O, OP: Parser<O>> {
+pub struct FollowedBy<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> {
     parser: FP,
     following: OP,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> FollowedBy<F, FP, O, OP> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> FollowedBy<F, FP, O, OP, I> {
     pub const fn new(parser: FP, following: OP) -> Self {
         Self {
             parser,
@@ -22,8 +22,10 @@ impl<F, FP: Parser<F>, O, OP: Parser<O>> FollowedBy<F, FP, O, OP> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> Parser<F> for FollowedBy<F, FP, O, OP> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<F> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> Parser<F, I>
+    for FollowedBy<F, FP, O, OP, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<F> {
         let parsed = self.parser.parse(input)?;
         self.following.parse(input)?;
         Ok(parsed)
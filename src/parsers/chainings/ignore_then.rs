@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`ignore_then`](`crate::ParserConstUtils::ignore_then`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct IgnoreThen<F, FP: Parser<F>, O, OP: Parser<O>> {
+pub struct IgnoreThen<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> {
     from: FP,
     to: OP,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> IgnoreThen<F, FP, O, OP> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> IgnoreThen<F, FP, O, OP, I> {
     pub const fn new(from: FP, to: OP) -> Self {
         Self {
             from,
@@ -22,8 +22,10 @@ impl<F, FP: Parser<F>, O, OP: Parser<O>> IgnoreThen<F, FP, O, OP> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> Parser<O> for IgnoreThen<F, FP, O, OP> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<O> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> Parser<O, I>
+    for IgnoreThen<F, FP, O, OP, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<O> {
         let from = self.from.parse(input)?;
         let to = self.to.parse(input)?;
         Ok(from.combine(to).map(|(_, to)| to))
@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`map`](`crate::ParserConstUtils::map`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Map<F, FP: Parser<F>, O, OF: Fn(F) -> O> {
+pub struct Map<F, FP: Parser<F, I>, O, OF: Fn(F) -> O, I: InputKind> {
     parser: FP,
     mapper: OF,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OF: Fn(F) -> O> Map<F, FP, O, OF> {
+impl<F, FP: Parser<F, I>, O, OF: Fn(F) -> O, I: InputKind> Map<F, FP, O, OF, I> {
     pub const fn new(from: FP, mapper: OF) -> Self {
         Self {
             parser: from,
@@ -22,8 +22,8 @@ impl<F, FP: Parser<F>, O, OF: Fn(F) -> O> Map<F, FP, O, OF> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OF: Fn(F) -> O> Parser<O> for Map<F, FP, O, OF> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<O> {
+impl<F, FP: Parser<F, I>, O, OF: Fn(F) -> O, I: InputKind> Parser<O, I> for Map<F, FP, O, OF, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<O> {
         Ok(self.parser.parse(input)?.map(&self.mapper))
     }
 }
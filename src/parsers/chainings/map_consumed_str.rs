@@ -2,17 +2,19 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, Span};
+use crate::{
+    ByteSource, ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
+};
 
 /// See [`map_consumed_str`](`crate::ParserConstUtils::map_consumed_str`)
 #[perfect_derive(Clone, Copy)]
-pub struct MapConsumedStr<F, FP: Parser<F>, O, OF: Fn(&str) -> O> {
+pub struct MapConsumedStr<F, FP: Parser<F, I>, O, OF: Fn(&str) -> O, I: InputKind> {
     parser: FP,
     mapper: OF,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OF: Fn(&str) -> O> MapConsumedStr<F, FP, O, OF> {
+impl<F, FP: Parser<F, I>, O, OF: Fn(&str) -> O, I: InputKind> MapConsumedStr<F, FP, O, OF, I> {
     pub const fn new(parser: FP, mapper: OF) -> Self {
         Self {
             parser,
@@ -22,10 +24,15 @@ impl<F, FP: Parser<F>, O, OF: Fn(&str) -> O> MapConsumedStr<F, FP, O, OF> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OF: Fn(&str) -> O> Parser<O> for MapConsumedStr<F, FP, O, OF> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<O> {
+impl<F, FP: Parser<F, I>, O, OF: Fn(&str) -> O, I: InputKind<Source: ByteSource>> Parser<O, I>
+    for MapConsumedStr<F, FP, O, OF, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<O> {
         let parsed = self.parser.parse(input)?;
-        let extract = input.extract(parsed.at);
+
+        let extract = input
+            .try_extract(parsed.at)
+            .ok_or_else(|| ParsingError::from_kind(parsed.at, ErrorKind::InvalidUtf8))?;
 
         Ok(Span::ate(parsed.at, (self.mapper)(extract)))
     }
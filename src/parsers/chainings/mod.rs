@@ -1,5 +1,6 @@
 mod and_then;
 mod and_then_or_critical;
+mod and_then_or_error;
 mod followed_by;
 mod ignore_then;
 mod map;
@@ -11,8 +12,8 @@ mod then;
 mod then_ignore;
 
 pub use self::{
-    and_then::AndThen, and_then_or_critical::AndThenOrCritical, followed_by::FollowedBy,
-    ignore_then::IgnoreThen, map::Map, map_consumed_str::MapConsumedStr,
+    and_then::AndThen, and_then_or_critical::AndThenOrCritical, and_then_or_error::AndThenOrError,
+    followed_by::FollowedBy, ignore_then::IgnoreThen, map::Map, map_consumed_str::MapConsumedStr,
     not_followed_by::NotFollowedBy, separated_by::SeparatedBy, surrounded_by::SurroundedBy,
     then::Then, then_ignore::ThenIgnore,
 };
@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`not_followed_by`](`crate::ParserConstUtils::not_followed_by`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct NotFollowedBy<F, FP: Parser<F>, O, OP: Parser<O>> {
+pub struct NotFollowedBy<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> {
     parser: FP,
     following: OP,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> NotFollowedBy<F, FP, O, OP> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> NotFollowedBy<F, FP, O, OP, I> {
     pub const fn new(parser: FP, following: OP) -> Self {
         Self {
             parser,
@@ -22,15 +22,16 @@ impl<F, FP: Parser<F>, O, OP: Parser<O>> NotFollowedBy<F, FP, O, OP> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> Parser<F> for NotFollowedBy<F, FP, O, OP> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<F> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> Parser<F, I>
+    for NotFollowedBy<F, FP, O, OP, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<F> {
         let parsed = self.parser.parse(input)?;
 
-        match self.following.parse(input) {
-            Ok(span) => Err(ParsingError::custom(
-                span.at,
-                "Parser should not have matched",
-            )),
+        match input.without_failure_tracking(|input| {
+            input.rollback_on_failure(|input| self.following.parse(input))
+        }) {
+            Ok(span) => Err(ParsingError::from_kind(span.at, ErrorKind::UnexpectedMatch)),
 
             Err(err) if err.is_critical() => Err(err),
 
@@ -1,25 +1,35 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
 use crate::{
-    Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError, Span,
+    ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
     containers::Container,
 };
 
 /// See [`separated_by`](`crate::ParserConstUtils::separated_by`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct SeparatedBy<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> {
+pub struct SeparatedBy<
+    T,
+    TP: Parser<T, I>,
+    S,
+    SP: Parser<S, I>,
+    C: Container<T>,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone = &'static str,
+> {
     parser: TP,
     separator: SP,
     min: Option<usize>,
     max: Option<usize>,
     exactly: Option<usize>,
-    critical_if_fails_after_sep: Option<&'static str>,
-    _p: PhantomData<(T, S, C)>,
+    critical_if_fails_after_sep: Option<M>,
+    _p: PhantomData<(T, S, C, I)>,
 }
 
-impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> SeparatedBy<T, TP, S, SP, C> {
+impl<T, TP: Parser<T, I>, S, SP: Parser<S, I>, C: Container<T>, I: InputKind>
+    SeparatedBy<T, TP, S, SP, C, I>
+{
     pub const fn new(parser: TP, separator: SP) -> Self {
         Self {
             parser,
@@ -31,7 +41,18 @@ impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> SeparatedBy<T, TP, S,
             _p: PhantomData,
         }
     }
+}
 
+impl<
+    T,
+    TP: Parser<T, I>,
+    S,
+    SP: Parser<S, I>,
+    C: Container<T>,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone,
+> SeparatedBy<T, TP, S, SP, C, I, M>
+{
     /// Require the parser to succeed at least the provided number of times (successively)
     ///
     /// Panics if [`Self::at_most`] or [`Self::exactly`] were called
@@ -94,36 +115,70 @@ impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> SeparatedBy<T, TP, S,
     ///
     /// If not set, no error will be returned, as the parser will simply stop with
     /// what it will have collected into the container
-    pub fn critical_if_fails_after_sep(mut self, msg: &'static str) -> Self {
-        self.critical_if_fails_after_sep = Some(msg);
-        self
+    pub fn critical_if_fails_after_sep<N: Into<Cow<'static, str>> + Clone>(
+        self,
+        msg: N,
+    ) -> SeparatedBy<T, TP, S, SP, C, I, N> {
+        let Self {
+            parser,
+            separator,
+            min,
+            max,
+            exactly,
+            critical_if_fails_after_sep: _,
+            _p,
+        } = self;
+
+        SeparatedBy {
+            parser,
+            separator,
+            min,
+            max,
+            exactly,
+            critical_if_fails_after_sep: Some(msg),
+            _p,
+        }
     }
 }
 
-impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> Parser<C>
-    for SeparatedBy<T, TP, S, SP, C>
+impl<
+    T,
+    TP: Parser<T, I>,
+    S,
+    SP: Parser<S, I>,
+    C: Container<T>,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone,
+> Parser<C, I> for SeparatedBy<T, TP, S, SP, C, I, M>
 {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<C> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<C> {
         let mut out = C::create();
         let mut size = 0;
         let mut ate = 0;
         let mut ate_separator = None;
         let start = input.at();
 
+        // Taken before the last separator, as it is discarded along with the element that fails after it
+        let mut checkpoint = input.session().checkpoint();
+
         let err = loop {
             let parsed = match self.parser.parse(input) {
                 Ok(parsed) => parsed,
                 Err(err) => {
                     if err.is_critical() {
                         return Err(err);
-                    } else if size > 0
-                        && let Some(msg) = self.critical_if_fails_after_sep
+                    }
+
+                    input.session().rollback(checkpoint);
+
+                    if size > 0
+                        && let Some(msg) = &self.critical_if_fails_after_sep
                     {
-                        return Err(ParsingError::custom(
+                        return Err(ParsingError::from_kind(
                             input.at().add(ate + ate_separator.unwrap_or(0)).range(0),
-                            "repeated value parser failed after separator",
+                            ErrorKind::FailedAfterSeparator,
                         )
-                        .criticalize(msg));
+                        .criticalize(msg.clone()));
                     } else {
                         break Some(err);
                     }
@@ -150,6 +205,16 @@ impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> Parser<C>
                 break None;
             }
 
+            // A repetition that consumed nothing would match the same way forever
+            if ate_separator == Some(0)
+                && parsed.at.len == 0
+                && size >= self.min.or(self.exactly).unwrap_or(0)
+            {
+                break None;
+            }
+
+            checkpoint = input.session().checkpoint();
+
             match self.separator.parse(input) {
                 Ok(parsed) => {
                     ate_separator = Some(parsed.at.len);
@@ -158,6 +223,7 @@ impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> Parser<C>
                     if err.is_critical() {
                         return Err(err);
                     } else {
+                        input.session().rollback(checkpoint);
                         break None;
                     }
                 }
@@ -168,7 +234,10 @@ impl<T, TP: Parser<T>, S, SP: Parser<S>, C: Container<T>> Parser<C>
             && size < min
         {
             return Err(err.filter(|_| size == 0).unwrap_or_else(|| {
-                ParsingError::custom(input.at().range(ate), "Not enough repetitions")
+                ParsingError::from_kind(
+                    input.at().range(ate),
+                    ErrorKind::NotEnoughRepetitions { min, found: size },
+                )
             }));
         }
 
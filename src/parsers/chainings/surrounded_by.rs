@@ -2,35 +2,118 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{ErrorKind, Found, InputKind, Message, Parser, ParserInput, ParserResult};
 
 /// See [`surrounded_by`](`crate::ParserConstUtils::surrounded_by`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct SurroundedBy<L, LP: Parser<L>, M, MP: Parser<M>, R, RP: Parser<R>> {
+pub struct SurroundedBy<L, LP: Parser<L, I>, M, MP: Parser<M, I>, R, RP: Parser<R, I>, I: InputKind>
+{
     left: LP,
     middle: MP,
     right: RP,
-    _p: PhantomData<(L, M, R)>,
+    delimiter_aware: bool,
+    delimiter_pairs: &'static [(char, char)],
+    _p: PhantomData<(L, M, R, I)>,
 }
 
-impl<L, LP: Parser<L>, M, MP: Parser<M>, R, RP: Parser<R>> SurroundedBy<L, LP, M, MP, R, RP> {
+impl<L, LP: Parser<L, I>, M, MP: Parser<M, I>, R, RP: Parser<R, I>, I: InputKind>
+    SurroundedBy<L, LP, M, MP, R, RP, I>
+{
     pub const fn new(left: LP, middle: MP, right: RP) -> Self {
         Self {
             left,
             middle,
             right,
+            delimiter_aware: false,
+            delimiter_pairs: &[('(', ')'), ('[', ']'), ('{', '}')],
             _p: PhantomData,
         }
     }
+
+    /// Treat the left and right parsers as opening and closing delimiters
+    ///
+    /// If the closing delimiter is missing after a successful opening one, a critical
+    /// "unclosed delimiter" error is returned, pointing at the opening delimiter.
+    /// If the opening delimiter opens one of the [delimiter pairs](SurroundedBy::delimiter_pairs)
+    /// and the closing delimiter of another pair is found instead, a critical
+    /// "mismatched closing delimiter" error is returned.
+    pub const fn delimiter_aware(mut self, enable: bool) -> Self {
+        self.delimiter_aware = enable;
+        self
+    }
+
+    /// Set the pairs of opening and closing delimiters used to detect mismatched delimiters
+    ///
+    /// Defaults to parentheses, square brackets and curly braces.
+    pub const fn delimiter_pairs(mut self, pairs: &'static [(char, char)]) -> Self {
+        self.delimiter_pairs = pairs;
+        self
+    }
+
+    /// Check if a closing delimiter closes another pair than the provided opening delimiter's
+    fn is_mismatched(&self, opener: &str, closer: char) -> bool {
+        let mut opener_chars = opener.chars();
+
+        let (Some(opener), None) = (opener_chars.next(), opener_chars.next()) else {
+            return false;
+        };
+
+        let opens_pair = self
+            .delimiter_pairs
+            .iter()
+            .any(|(open, close)| *open == opener && *close != closer);
+
+        opens_pair
+            && self
+                .delimiter_pairs
+                .iter()
+                .any(|(_, close)| *close == closer)
+    }
 }
 
-impl<L, LP: Parser<L>, M, MP: Parser<M>, R, RP: Parser<R>> Parser<M>
-    for SurroundedBy<L, LP, M, MP, R, RP>
+impl<L, LP: Parser<L, I>, M, MP: Parser<M, I>, R, RP: Parser<R, I>, I: InputKind> Parser<M, I>
+    for SurroundedBy<L, LP, M, MP, R, RP, I>
 {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<M> {
-        let start = self.left.parse(input).unwrap();
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<M> {
+        let start = self.left.parse(input)?;
         let middle = self.middle.parse(input)?;
-        let end = self.right.parse(input).unwrap();
+
+        let end = self.right.parse(input).map_err(|err| {
+            if !self.delimiter_aware || err.is_critical() {
+                return err;
+            }
+
+            let opener = input.describe(start.at);
+
+            match err.inner().found() {
+                Some(Found::Char(closer)) if self.is_mismatched(&opener, *closer) => {
+                    let closer = *closer;
+
+                    err.criticalize_kind(ErrorKind::MismatchedDelimiter {
+                        opener: opener.as_ref().into(),
+                        closer,
+                    })
+                    .with_secondary_label(
+                        start.at,
+                        Message::MismatchedDelimiterLabel {
+                            opener: opener.as_ref().into(),
+                            closer,
+                        },
+                    )
+                }
+
+                _ => err
+                    .criticalize_kind(ErrorKind::UnclosedDelimiter {
+                        opener: opener.as_ref().into(),
+                    })
+                    .with_secondary_label(
+                        start.at,
+                        Message::UnclosedDelimiterLabel {
+                            opener: opener.as_ref().into(),
+                        },
+                    ),
+            }
+        })?;
 
         Ok(start
             .combine(middle)
@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`then`](`crate::ParserConstUtils::then`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Then<F, FP: Parser<F>, O, OP: Parser<O>> {
+pub struct Then<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> {
     from: FP,
     to: OP,
-    _p: PhantomData<(F, O)>,
+    _p: PhantomData<(F, O, I)>,
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> Then<F, FP, O, OP> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> Then<F, FP, O, OP, I> {
     pub const fn new(from: FP, to: OP) -> Self {
         Self {
             from,
@@ -22,8 +22,10 @@ impl<F, FP: Parser<F>, O, OP: Parser<O>> Then<F, FP, O, OP> {
     }
 }
 
-impl<F, FP: Parser<F>, O, OP: Parser<O>> Parser<(F, O)> for Then<F, FP, O, OP> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<(F, O)> {
+impl<F, FP: Parser<F, I>, O, OP: Parser<O, I>, I: InputKind> Parser<(F, O), I>
+    for Then<F, FP, O, OP, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<(F, O)> {
         let from = self.from.parse(input)?;
         let to = self.to.parse(input)?;
         Ok(from.combine(to))
@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`choice`](`crate::parsers::helpers::choice`)
 #[perfect_derive(Clone, Copy)]
-pub struct Choice<T: IntoChoice<O>, O> {
+pub struct Choice<T: IntoChoice<O, I>, O, I: InputKind> {
     parsers: T,
-    _p: PhantomData<O>,
+    _p: PhantomData<(O, I)>,
 }
 
-impl<T: IntoChoice<O>, O> Choice<T, O> {
+impl<T: IntoChoice<O, I>, O, I: InputKind> Choice<T, O, I> {
     pub const fn new(parsers: T) -> Self {
         Self {
             parsers,
@@ -22,8 +22,8 @@ impl<T: IntoChoice<O>, O> Choice<T, O> {
 
 /// Constraint type to ensure that all parsers provided to a [`Choice`]
 /// do implement the [`Parser`] trait and that they all output the same exact type
-pub trait IntoChoice<O> {
-    fn into_choice(self) -> Choice<Self, O>
+pub trait IntoChoice<O, I: InputKind> {
+    fn into_choice(self) -> Choice<Self, O, I>
     where
         Self: Sized;
 }
@@ -37,29 +37,38 @@ macro_rules! _impl_choice {
     };
 
     (~ $($X: ident)+) => {
-        impl<$($X: Parser<Output>),+, Output> IntoChoice<Output> for ($($X,)+) {
-            fn into_choice(self) -> Choice<Self, Output> where Self: Sized {
+        impl<$($X: Parser<Output, Input>),+, Output, Input: InputKind> IntoChoice<Output, Input> for ($($X,)+) {
+            fn into_choice(self) -> Choice<Self, Output, Input> where Self: Sized {
                 Choice::new(self)
             }
         }
 
-        impl<$($X: Parser<Output>),+, Output> Parser<Output> for Choice<($($X,)+), Output> {
-            fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<Output> {
-                // let mut errors = vec![];
-
+        impl<$($X: Parser<Output, Input>),+, Output, Input: InputKind> Parser<Output, Input> for Choice<($($X,)+), Output, Input> {
+            fn parse_inner(&self, input: &mut ParserInput<Input>) -> ParserResult<Output> {
                 #[allow(non_snake_case)]
                 let Choice { parsers: ($($X,)+), _p: _ } = &self;
 
+                let mut error: Option<ParsingError> = None;
+
                 $(
                     // TODO: "parse_inner" instead?
-                    match $X.parse(input) {
+                    match input.rollback_on_failure(|input| $X.parse(input)) {
                         Ok(result) => return Ok(result),
                         Err(err) if err.is_critical() => return Err(err),
-                        Err(_) => {} // errors.push(err)
+                        Err(err) => {
+                            error = Some(match error {
+                                None => err,
+                                Some(prev) => prev.merge_alternative(err),
+                            });
+                        }
                     }
                 )+
 
-                Err(ParsingError::custom(input.at().range(0), "None of choices matched"))
+                Err(match error {
+                    // Suggest the closest literal if a word was found instead
+                    Some(err) => err.with_closest_str_suggestion(),
+                    None => ParsingError::from_kind(input.at().range(0), ErrorKind::NoChoiceMatched),
+                })
             }
         }
     }
@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, Span};
 
 /// See [`lookahead`](`crate::parsers::helpers::lookahead`)
 #[perfect_derive(Clone, Copy)]
-pub struct Lookahead<T, P: Parser<T>> {
+pub struct Lookahead<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Lookahead<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> Lookahead<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,10 +20,17 @@ impl<T, P: Parser<T>> Lookahead<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<T> for Lookahead<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<T, I> for Lookahead<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         let mut input_copy = *input;
+
+        let checkpoint = input.session().checkpoint();
+
         let parsed = self.parser.parse(&mut input_copy)?;
+
+        // Discard what the parser recorded, as the input isn't consumed
+        input.session().rollback(checkpoint);
+
         Ok(Span::ate(input.range(0), parsed.data))
     }
 }
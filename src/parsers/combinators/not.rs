@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError, Span};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
 
 /// See [`not`](`crate::parsers::helpers::not`)
 #[perfect_derive(Clone, Copy)]
-pub struct Not<T, P: Parser<T>> {
+pub struct Not<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Not<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> Not<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,13 +20,12 @@ impl<T, P: Parser<T>> Not<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<()> for Not<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
-        match self.parser.parse(input) {
-            Ok(span) => Err(ParsingError::custom(
-                span.at,
-                "Parser should not have matched",
-            )),
+impl<T, P: Parser<T, I>, I: InputKind> Parser<(), I> for Not<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
+        match input.without_failure_tracking(|input| {
+            input.rollback_on_failure(|input| self.parser.parse(input))
+        }) {
+            Ok(span) => Err(ParsingError::from_kind(span.at, ErrorKind::UnexpectedMatch)),
 
             Err(_) => Ok(Span::ate(input.range(0), ())),
         }
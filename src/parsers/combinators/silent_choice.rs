@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`silent_choice`](`crate::parsers::helpers::silent_choice`)
 #[perfect_derive(Clone, Copy)]
-pub struct SilentChoice<T: IntoSilentChoice<Outputs>, Outputs> {
+pub struct SilentChoice<T: IntoSilentChoice<Outputs, I>, Outputs, I: InputKind> {
     parsers: T,
-    _p: PhantomData<Outputs>,
+    _p: PhantomData<(Outputs, I)>,
 }
 
-impl<T: IntoSilentChoice<Outputs>, Outputs> SilentChoice<T, Outputs> {
+impl<T: IntoSilentChoice<Outputs, I>, Outputs, I: InputKind> SilentChoice<T, Outputs, I> {
     pub const fn new(parsers: T) -> Self {
         Self {
             parsers,
@@ -22,8 +22,8 @@ impl<T: IntoSilentChoice<Outputs>, Outputs> SilentChoice<T, Outputs> {
 
 /// Constraint type to ensure that all parsers provided to a [`SilentChoice`]
 /// do implement the [`Parser`] trait
-pub trait IntoSilentChoice<Outputs> {
-    fn into_silent_choice(self) -> SilentChoice<Self, Outputs>
+pub trait IntoSilentChoice<Outputs, I: InputKind> {
+    fn into_silent_choice(self) -> SilentChoice<Self, Outputs, I>
     where
         Self: Sized;
 }
@@ -37,28 +37,37 @@ macro_rules! _impl_silent_choice {
     };
 
     (~ $($X: ident[$Xo: ident],)+) => {
-        impl<$($X: Parser<$Xo>, $Xo),+> IntoSilentChoice<($($Xo,)+)> for ($($X,)+) {
-            fn into_silent_choice(self) -> SilentChoice<Self, ($($Xo,)+)> where Self: Sized {
-                SilentChoice::<Self, ($($Xo,)+)>::new(self)
+        impl<$($X: Parser<$Xo, Input>, $Xo),+, Input: InputKind> IntoSilentChoice<($($Xo,)+), Input> for ($($X,)+) {
+            fn into_silent_choice(self) -> SilentChoice<Self, ($($Xo,)+), Input> where Self: Sized {
+                SilentChoice::<Self, ($($Xo,)+), Input>::new(self)
             }
         }
 
-        impl<$($X: Parser<$Xo>, $Xo),+> Parser<()> for SilentChoice<($($X,)+), ($($Xo,)+)> {
-            fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+        impl<$($X: Parser<$Xo, Input>, $Xo),+, Input: InputKind> Parser<(), Input> for SilentChoice<($($X,)+), ($($Xo,)+), Input> {
+            fn parse_inner(&self, input: &mut ParserInput<Input>) -> ParserResult<()> {
                 #[allow(non_snake_case)]
                 let SilentChoice { parsers: ($($X,)+), _p: _ } = &self;
 
-                // let mut errors = vec![];
+                let mut error: Option<ParsingError> = None;
 
                 $(
-                    match $X.parse(input) {
+                    match input.rollback_on_failure(|input| $X.parse(input)) {
                         Ok(result) => return Ok(result.forge_here(())),
                         Err(err) if err.is_critical() => return Err(err),
-                        Err(_) => {} // errors.push(err)
+                        Err(err) => {
+                            error = Some(match error {
+                                None => err,
+                                Some(prev) => prev.merge_alternative(err),
+                            });
+                        }
                     }
                 )+
 
-                Err(ParsingError::custom(input.at().range(0), "None of choices matched"))
+                Err(match error {
+                    // Suggest the closest literal if a word was found instead
+                    Some(err) => err.with_closest_str_suggestion(),
+                    None => ParsingError::from_kind(input.at().range(0), ErrorKind::NoChoiceMatched),
+                })
             }
         }
     }
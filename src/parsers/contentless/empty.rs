@@ -1,4 +1,4 @@
-use crate::{Parser, ParserInput, ParserResult, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, Span};
 
 /// See [`empty`](`crate::parsers::helpers::empty`)
 #[derive(Clone, Copy)]
@@ -16,8 +16,8 @@ impl Default for Empty {
     }
 }
 
-impl Parser<()> for Empty {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+impl<I: InputKind> Parser<(), I> for Empty {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
         Ok(Span::ate(input.range(0), ()))
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError, Span};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
 
 /// See [`end`](`crate::parsers::helpers::end`)
 #[derive(Clone, Copy)]
@@ -16,14 +16,14 @@ impl Default for End {
     }
 }
 
-impl Parser<()> for End {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
-        if input.inner().is_empty() {
+impl<I: InputKind> Parser<(), I> for End {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
+        if input.is_empty() {
             Ok(Span::ate(input.range(0), ()))
         } else {
-            Err(ParsingError::custom(
+            Err(ParsingError::from_kind(
                 input.at().range(0),
-                "Expected end of input",
+                ErrorKind::EndOfInput,
             ))
         }
     }
@@ -1,4 +1,4 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError, Span};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
 
 /// See [`start`](`crate::parsers::helpers::start`)
 #[derive(Clone, Copy)]
@@ -16,14 +16,14 @@ impl Default for Start {
     }
 }
 
-impl Parser<()> for Start {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+impl<I: InputKind> Parser<(), I> for Start {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
         if input.offset() == 0 {
             Ok(Span::ate(input.range(0), ()))
         } else {
-            Err(ParsingError::custom(
+            Err(ParsingError::from_kind(
                 input.at().range(0),
-                "Expected start of input",
+                ErrorKind::StartOfInput,
             ))
         }
     }
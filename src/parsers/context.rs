@@ -1,28 +1,18 @@
-use std::{any::Any, marker::PhantomData};
+use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserResult, ParsingError, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, Span};
 
 /// See [`get_context`](`crate::parsers::helpers::get_context`)
 #[perfect_derive(Clone, Copy)]
 pub struct GetContext<C> {
     _c: PhantomData<C>,
-    not_critical: bool,
 }
 
 impl<C> GetContext<C> {
     pub const fn new() -> Self {
-        Self {
-            _c: PhantomData,
-            not_critical: false,
-        }
-    }
-
-    /// Dont't generate a critical error when failing
-    pub const fn not_critical(mut self) -> Self {
-        self.not_critical = true;
-        self
+        Self { _c: PhantomData }
     }
 }
 
@@ -32,30 +22,81 @@ impl<C> Default for GetContext<C> {
     }
 }
 
-impl<C: Any> Parser<Box<C>> for GetContext<C> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<Box<C>> {
-        let logic = || -> Result<_, _> {
-            let get_ctx = input.ctx().ok_or(
-                "Internal error: Expected a context in the parser input, but context is missing",
-            )?;
+impl<C: Copy, I: InputKind<Context = C>> Parser<C, I> for GetContext<C> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<C> {
+        Ok(Span {
+            at: input.range(0),
+            data: input.context(),
+        })
+    }
+}
+
+/// See [`with_context`](`crate::ParserConstUtils::with_context`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct WithContext<T, P: Parser<T, I::WithContext<C>>, C: Copy, I: InputKind> {
+    parser: P,
+    ctx: C,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I::WithContext<C>>, C: Copy, I: InputKind> WithContext<T, P, C, I> {
+    pub const fn new(parser: P, ctx: C) -> Self {
+        Self {
+            parser,
+            ctx,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I::WithContext<C>>, C: Copy, I: InputKind> Parser<T, I>
+    for WithContext<T, P, C, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let parsed = self.parser.parse(&mut input.with_context(self.ctx))?;
+
+        input.advance(parsed.at);
+
+        Ok(parsed)
+    }
+}
+
+/// See [`map_context`](`crate::ParserConstUtils::map_context`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct MapContext<
+    T,
+    P: Parser<T, I::WithContext<D>>,
+    D: Copy,
+    F: Fn(I::Context) -> D,
+    I: InputKind,
+> {
+    parser: P,
+    mapper: F,
+    _p: PhantomData<(T, D, I)>,
+}
+
+impl<T, P: Parser<T, I::WithContext<D>>, D: Copy, F: Fn(I::Context) -> D, I: InputKind>
+    MapContext<T, P, D, F, I>
+{
+    pub const fn new(parser: P, mapper: F) -> Self {
+        Self {
+            parser,
+            mapper,
+            _p: PhantomData,
+        }
+    }
+}
 
-            <Box<dyn Any>>::downcast::<C>(get_ctx())
-                .map_err(|_| "Internal error: Context found in the parser input does not have the expected type")
-        };
+impl<T, P: Parser<T, I::WithContext<D>>, D: Copy, F: Fn(I::Context) -> D, I: InputKind> Parser<T, I>
+    for MapContext<T, P, D, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let mapped = (self.mapper)(input.context());
 
-        logic()
-            .map(|ctx| Span {
-                at: input.range(0),
-                data: ctx,
-            })
-            .map_err(|msg| {
-                let mut err = ParsingError::custom(input.range(0), msg);
+        let parsed = self.parser.parse(&mut input.with_context(mapped))?;
 
-                if !self.not_critical {
-                    err = err.criticalize(msg);
-                }
+        input.advance(parsed.at);
 
-                err
-            })
+        Ok(parsed)
     }
 }
@@ -1,21 +1,29 @@
+use std::marker::PhantomData;
+
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`custom`](`crate::parsers::helpers::custom`)
 #[perfect_derive(Clone, Copy)]
-pub struct Custom<F: Fn(&mut ParserInput) -> ParserResult<O>, O> {
+pub struct Custom<F: Fn(&mut ParserInput<I>) -> ParserResult<O>, O, I: InputKind> {
     func: F,
+    _p: PhantomData<I>,
 }
 
-impl<F: Fn(&mut ParserInput) -> ParserResult<O>, O> Custom<F, O> {
+impl<F: Fn(&mut ParserInput<I>) -> ParserResult<O>, O, I: InputKind> Custom<F, O, I> {
     pub const fn new(func: F) -> Self {
-        Self { func }
+        Self {
+            func,
+            _p: PhantomData,
+        }
     }
 }
 
-impl<F: Fn(&mut ParserInput) -> ParserResult<O>, O> Parser<O> for Custom<F, O> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<O> {
+impl<F: Fn(&mut ParserInput<I>) -> ParserResult<O>, O, I: InputKind> Parser<O, I>
+    for Custom<F, O, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<O> {
         (self.func)(input)
     }
 }
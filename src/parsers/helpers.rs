@@ -2,14 +2,16 @@
 //! A collection of helper functions to build parsers easily
 //!
 
-use std::{any::Any, collections::HashSet};
+use std::{collections::HashSet, fmt};
 
-use crate::{ParserInput, ParserResult, parser::Parser};
+use crate::{InputKind, ParserInput, ParserResult, parser::Parser};
 
 use super::{
-    Char, Choice, Custom, Digit, Empty, End, Filter, GetContext, IntoChoice, IntoSilentChoice,
-    Just, LazilyDefined, Lookahead, Newline, Not, OneOfChars, SilentChoice, Start, StaticRef,
-    ToDefine, ToDefineShared, Whitespace, Whitespaces,
+    BinaryInt, Byte, Char, Choice, Custom, Digit, Empty, End, Endianness, Filter, FilterByte,
+    GetContext, Int, IntoChoice, IntoSilentChoice, Just, JustBytes, JustToken, LazilyDefined,
+    LengthPrefixed, Lookahead, Newline, Not, OneOfChars, Select, SignedLeb128, SilentChoice,
+    SkipUntil, Start, StaticRef, TakeBytes, ToDefine, ToDefineShared, UnsignedLeb128, Whitespace,
+    Whitespaces,
 };
 
 /// Match the start of the input (doesn't consume the input)
@@ -73,27 +75,112 @@ pub const fn filter<F: Fn(char) -> bool>(func: F) -> Filter<F> {
     Filter::new(func)
 }
 
+/// Match the provided byte
+pub const fn byte(byte: u8) -> Byte {
+    Byte::new(byte)
+}
+
+/// Match exactly the provided bytes
+pub const fn just_bytes(bytes: &'static [u8]) -> JustBytes {
+    JustBytes::new(bytes)
+}
+
+/// Match any byte that passes the provided filter
+pub const fn filter_byte<F: Fn(u8) -> bool>(func: F) -> FilterByte<F> {
+    FilterByte::new(func)
+}
+
+/// Match a big-endian fixed-width integer
+pub const fn int_be<N: BinaryInt>() -> Int<N> {
+    Int::new(Endianness::Big)
+}
+
+/// Match a little-endian fixed-width integer
+pub const fn int_le<N: BinaryInt>() -> Int<N> {
+    Int::new(Endianness::Little)
+}
+
+/// Match an unsigned LEB128-encoded integer
+pub const fn uleb128() -> UnsignedLeb128 {
+    UnsignedLeb128
+}
+
+/// Match a signed LEB128-encoded integer
+pub const fn sleb128() -> SignedLeb128 {
+    SignedLeb128
+}
+
+/// Match exactly the provided number of bytes
+pub const fn take_bytes(len: usize) -> TakeBytes {
+    TakeBytes::new(Some(len))
+}
+
+/// Match all the remaining bytes
+pub const fn rest_bytes() -> TakeBytes {
+    TakeBytes::new(None)
+}
+
+/// Match a field whose length (in bytes) is parsed first
+///
+/// The content parser only sees the field's bytes, and must consume all of them.
+pub const fn length_prefixed<
+    L: TryInto<usize>,
+    LP: Parser<L, I>,
+    T,
+    P: Parser<T, I>,
+    I: InputKind,
+>(
+    length: LP,
+    content: P,
+) -> LengthPrefixed<L, LP, T, P, I> {
+    LengthPrefixed::new(length, content)
+}
+
+/// Match the provided token
+///
+/// Can only be used on inputs made of tokens of the same type (see [`ParserInput::new_tokens`]):
+///
+/// ```compile_fail
+/// use parsy::{ParserNonConstUtils, parsers::helpers::token};
+///
+/// token(',').parse_str(",");
+/// ```
+pub const fn token<T: PartialEq + Clone + fmt::Debug>(token: T) -> JustToken<T> {
+    JustToken::new(token)
+}
+
+/// Match any token the provided function selects, returning the value it produced
+///
+/// Can only be used on inputs made of tokens (see [`ParserInput::new_tokens`])
+pub const fn select<T: fmt::Debug, O, F: Fn(&T) -> Option<O>>(func: F) -> Select<T, O, F> {
+    Select::new(func)
+}
+
 /// Create a parser that returns the value of the first parser to succeed in a set
 ///
 /// Parsers are in the order they were provided during initialization
-pub const fn choice<O, T: IntoChoice<O>>(parsers: T) -> Choice<T, O> {
+pub const fn choice<O, I: InputKind, T: IntoChoice<O, I>>(parsers: T) -> Choice<T, O, I> {
     Choice::new(parsers)
 }
 
 /// Equivalent to [`choice`], but does ignores the parse value and return a `()` instead
 ///
 /// Unlike [`choice`], allows using parser that evaluated to different parsed types
-pub const fn silent_choice<O, T: IntoSilentChoice<O>>(parsers: T) -> SilentChoice<T, O> {
+pub const fn silent_choice<O, I: InputKind, T: IntoSilentChoice<O, I>>(
+    parsers: T,
+) -> SilentChoice<T, O, I> {
     SilentChoice::new(parsers)
 }
 
 /// Succeed if and only if the provided parser fails, doesn't consume the input
-pub const fn not<T, P: Parser<T>>(parser: P) -> Not<T, P> {
+pub const fn not<T, P: Parser<T, I>, I: InputKind>(parser: P) -> Not<T, P, I> {
     Not::new(parser)
 }
 
 /// Succeed if and only if the provided parser suceeds, but doesn't consume the input
-pub const fn lookahead<T, P: Parser<T>>(parser: P) -> Lookahead<T, P> {
+///
+/// Diagnostics and user state updates recorded by the parser are discarded
+pub const fn lookahead<T, P: Parser<T, I>, I: InputKind>(parser: P) -> Lookahead<T, P, I> {
     Lookahead::new(parser)
 }
 
@@ -102,12 +189,12 @@ pub const fn lookahead<T, P: Parser<T>>(parser: P) -> Lookahead<T, P> {
 /// For a thread-safe variant, see [`to_define_shared`].
 ///
 /// To define a parser recursively, see [`recursive`]
-pub fn to_define<T>() -> ToDefine<T> {
+pub fn to_define<T, I: InputKind>() -> ToDefine<T, I> {
     ToDefine::new()
 }
 
 /// Thread-safe equivalent of [`to_define`]
-pub fn to_define_shared<T>() -> ToDefineShared<T> {
+pub fn to_define_shared<T, I: InputKind>() -> ToDefineShared<T, I> {
     ToDefineShared::new()
 }
 
@@ -116,17 +203,19 @@ pub fn to_define_shared<T>() -> ToDefineShared<T> {
 /// For a a thread-safe variant, see [`recursive_shared`]
 ///
 /// Uses [`to_define`] under the hood
-pub fn recursive<T, P: Parser<T> + 'static>(decl: impl FnOnce(ToDefine<T>) -> P) -> ToDefine<T> {
-    let parser = to_define::<T>();
+pub fn recursive<T, I: InputKind, P: Parser<T, I> + 'static>(
+    decl: impl FnOnce(ToDefine<T, I>) -> P,
+) -> ToDefine<T, I> {
+    let parser = to_define();
     parser.define(decl(parser.clone()));
     parser
 }
 
 /// Thread-safe equivalent of [`recursive`]
-pub fn recursive_shared<T, P: Parser<T> + Send + Sync + 'static>(
-    decl: impl FnOnce(ToDefineShared<T>) -> P,
-) -> ToDefineShared<T> {
-    let parser = to_define_shared::<T>();
+pub fn recursive_shared<T, I: InputKind, P: Parser<T, I> + Send + Sync + 'static>(
+    decl: impl FnOnce(ToDefineShared<T, I>) -> P,
+) -> ToDefineShared<T, I> {
+    let parser = to_define_shared();
     parser.define(decl(parser.clone()));
     parser
 }
@@ -134,24 +223,45 @@ pub fn recursive_shared<T, P: Parser<T> + Send + Sync + 'static>(
 /// Define a parser that will be evaluated only once and shared among all threads afterwards
 ///
 /// Only accepts a dynamic parser wrapped in a [`Box`], see [`crate::ParserNonConstUtils::erase_type`]
-pub const fn lazily_define<T>(setup: fn() -> Box<dyn Parser<T> + Send + Sync>) -> LazilyDefined<T> {
+pub const fn lazily_define<T, I: InputKind>(
+    setup: fn() -> Box<dyn Parser<T, I> + Send + Sync>,
+) -> LazilyDefined<T, I> {
     LazilyDefined::new(setup)
 }
 
 /// Use a parser from a non-movable type (e;g. [`LazilyDefined`])
-pub const fn static_ref<T, P: Parser<T>>(parser: &'static P) -> StaticRef<T, P> {
+pub const fn static_ref<T, P: Parser<T, I>, I: InputKind>(
+    parser: &'static P,
+) -> StaticRef<T, P, I> {
     StaticRef::new(parser)
 }
 
 /// Create a parser using a custom parsing function
-pub const fn custom<F: Fn(&mut ParserInput) -> ParserResult<O>, O>(func: F) -> Custom<F, O> {
+pub const fn custom<F: Fn(&mut ParserInput<I>) -> ParserResult<O>, O, I: InputKind>(
+    func: F,
+) -> Custom<F, O, I> {
     Custom::new(func)
 }
 
-/// Fetch the parser's context (see [`ParserInput::new_with_ctx`]) with the provided type
+/// Fetch the parser's context (see [`ParserInput::with_context`]) with the provided type
+///
+/// The parser can only be used on inputs with a context of this type:
+///
+/// ```compile_fail
+/// use parsy::{ParserNonConstUtils, parsers::helpers::get_context};
 ///
-/// If the context is missing or has a different type than the provided one, a critical error
-/// will be emitted
-pub const fn get_context<C: Any>() -> GetContext<C> {
+/// get_context::<&String>().parse_str_with_context("", &0u32);
+/// ```
+pub const fn get_context<C: Copy>() -> GetContext<C> {
     GetContext::new()
 }
+
+/// Recovery strategy that skips the input until the provided parser matches
+///
+/// The input matched by the provided parser is skipped as well.
+/// The placeholder value is the type's default one.
+///
+/// See [`recover_with`](`crate::ParserConstUtils::recover_with`)
+pub const fn skip_until<U, P: Parser<U, I>, I: InputKind>(until: P) -> SkipUntil<U, P, I> {
+    SkipUntil::new(until)
+}
@@ -1,16 +1,19 @@
+mod binary;
 mod chainings;
 mod chars;
 mod combinators;
 mod contentless;
 mod context;
 mod custom;
+mod recovery;
 mod tails;
 mod textuals;
 mod timed;
+mod tokens;
 
 pub mod helpers;
 
 pub use self::{
-    chainings::*, chars::*, combinators::*, contentless::*, context::*, custom::*, tails::*,
-    textuals::*, timed::*,
+    binary::*, chainings::*, chars::*, combinators::*, contentless::*, context::*, custom::*,
+    recovery::*, tails::*, textuals::*, timed::*, tokens::*,
 };
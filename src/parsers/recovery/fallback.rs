@@ -0,0 +1,31 @@
+use crate::{InputKind, ParserInput, ParsingError, Span};
+
+use super::RecoveryStrategy;
+
+/// See [`recover_with_fallback`](`crate::ParserConstUtils::recover_with_fallback`)
+#[derive(Debug, Clone, Copy)]
+pub struct Fallback<T: Clone> {
+    value: T,
+}
+
+impl<T: Clone> Fallback<T> {
+    pub const fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T: Clone, I: InputKind> RecoveryStrategy<T, I> for Fallback<T> {
+    fn recover(&self, input: &mut ParserInput<I>, err: &ParsingError) -> Option<Span<T>> {
+        let at = err.inner().at();
+        let len = (at.start.offset + at.len).checked_sub(input.offset())?;
+
+        // Recovering without consuming anything would make repetitions loop forever
+        if len == 0 {
+            return None;
+        }
+
+        let skipped = input.try_skip(len)?;
+
+        Some(Span::ate(skipped, self.value.clone()))
+    }
+}
@@ -0,0 +1,18 @@
+mod fallback;
+mod nested_delimiters;
+mod skip_until;
+
+pub use self::{fallback::Fallback, nested_delimiters::NestedDelimiters, skip_until::SkipUntil};
+
+use crate::{InputKind, ParserInput, ParsingError, Span, TextInput};
+
+/// Strategy to recover from a critical failure
+///
+/// See [`recover_with`](`crate::ParserConstUtils::recover_with`)
+pub trait RecoveryStrategy<T, I: InputKind = TextInput> {
+    /// Try to recover from the provided error
+    ///
+    /// The input is located where the failed parser started. In case of success,
+    /// the returned span must start at this location and cover all the skipped input.
+    fn recover(&self, input: &mut ParserInput<I>, err: &ParsingError) -> Option<Span<T>>;
+}
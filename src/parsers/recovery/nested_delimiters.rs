@@ -0,0 +1,43 @@
+use crate::{ByteSource, InputKind, ParserInput, ParsingError, Span};
+
+use super::RecoveryStrategy;
+
+/// See [`recover_nested`](`crate::ParserConstUtils::recover_nested`)
+#[derive(Debug, Clone, Copy)]
+pub struct NestedDelimiters {
+    open: char,
+    close: char,
+}
+
+impl NestedDelimiters {
+    pub const fn new(open: char, close: char) -> Self {
+        Self { open, close }
+    }
+}
+
+impl<T: Default, I: InputKind<Source: ByteSource>> RecoveryStrategy<T, I> for NestedDelimiters {
+    fn recover(&self, input: &mut ParserInput<I>, _: &ParsingError) -> Option<Span<T>> {
+        let start = input.at();
+
+        if input.try_eat_char()?.data != self.open {
+            return None;
+        }
+
+        let mut depth = 1;
+
+        while depth > 0 {
+            let c = input.try_eat_char()?.data;
+
+            if c == self.open {
+                depth += 1;
+            } else if c == self.close {
+                depth -= 1;
+            }
+        }
+
+        Some(Span::ate(
+            start.range(input.offset() - start.offset()),
+            T::default(),
+        ))
+    }
+}
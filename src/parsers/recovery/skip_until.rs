@@ -0,0 +1,48 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{InputKind, Parser, ParserInput, ParsingError, Span};
+
+use super::RecoveryStrategy;
+
+/// See [`skip_until`](`crate::parsers::helpers::skip_until`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct SkipUntil<U, P: Parser<U, I>, I: InputKind> {
+    until: P,
+    _p: PhantomData<(U, I)>,
+}
+
+impl<U, P: Parser<U, I>, I: InputKind> SkipUntil<U, P, I> {
+    pub const fn new(until: P) -> Self {
+        Self {
+            until,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T: Default, U, P: Parser<U, I>, I: InputKind> RecoveryStrategy<T, I> for SkipUntil<U, P, I> {
+    fn recover(&self, input: &mut ParserInput<I>, _: &ParsingError) -> Option<Span<T>> {
+        let start = input.at();
+
+        loop {
+            match input.without_failure_tracking(|input| {
+                input.rollback_on_failure(|input| self.until.parse(input))
+            }) {
+                Ok(_) => {
+                    return Some(Span::ate(
+                        start.range(input.offset() - start.offset()),
+                        T::default(),
+                    ));
+                }
+
+                Err(err) if err.is_critical() => return None,
+
+                Err(_) => {
+                    input.try_skip_item()?;
+                }
+            }
+        }
+    }
+}
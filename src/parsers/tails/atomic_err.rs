@@ -1,31 +1,68 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`atomic_err`](`crate::ParserConstUtils::atomic_err`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct AtomicErr<T, P: Parser<T>> {
+pub struct AtomicErr<
+    T,
+    P: Parser<T, I>,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone = &'static str,
+> {
     parser: P,
-    message: &'static str,
-    _p: PhantomData<T>,
+    message: M,
+    code: Option<&'static str>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> AtomicErr<T, P> {
-    pub const fn new(parser: P, message: &'static str) -> Self {
+impl<T, P: Parser<T, I>, I: InputKind, M: Into<Cow<'static, str>> + Clone> AtomicErr<T, P, I, M> {
+    pub const fn new(parser: P, message: M) -> Self {
         Self {
             parser,
             message,
+            code: None,
             _p: PhantomData,
         }
     }
+
+    /// Attach a code to the error (see [`ParsingError::with_code`])
+    pub const fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
 }
 
-impl<T, P: Parser<T>> Parser<T> for AtomicErr<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
-        self.parser.parse(input).map_err(|err| {
-            ParsingError::custom(err.inner().at(), self.message).with_atomic_error(self.message)
-        })
+impl<T, P: Parser<T, I>, I: InputKind, M: Into<Cow<'static, str>> + Clone> Parser<T, I>
+    for AtomicErr<T, P, I, M>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        // Nested failures are discarded from the session as well
+        input
+            .without_failure_tracking(|input| self.parser.parse(input))
+            .map_err(|err| {
+                let message = self.message.clone().into();
+
+                let atomic = ParsingError::custom(err.inner().at(), message.clone())
+                    .with_atomic_error(message);
+
+                let atomic = match err.inner().found() {
+                    Some(found) => atomic.with_found(found.clone()),
+                    None => atomic,
+                };
+
+                let atomic = match self.code {
+                    Some(code) => atomic.with_code(code),
+                    None => atomic,
+                };
+
+                // Keep the labelled parsers the error went through
+                err.context()
+                    .iter()
+                    .cloned()
+                    .fold(atomic, ParsingError::with_context_frame)
+            })
     }
 }
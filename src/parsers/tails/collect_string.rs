@@ -2,16 +2,18 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, Span};
+use crate::{
+    ByteSource, ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
+};
 
 /// See [`collect_string`](`crate::ParserConstUtils::collect_string`)
 #[perfect_derive(Clone, Copy)]
-pub struct CollectString<T, P: Parser<T>> {
+pub struct CollectString<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> CollectString<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> CollectString<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,10 +22,16 @@ impl<T, P: Parser<T>> CollectString<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<String> for CollectString<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<String> {
+impl<T, P: Parser<T, I>, I: InputKind<Source: ByteSource>> Parser<String, I>
+    for CollectString<T, P, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<String> {
         let parsed = self.parser.parse(input)?;
 
-        Ok(Span::ate(parsed.at, input.extract(parsed.at).to_string()))
+        let extract = input
+            .try_extract(parsed.at)
+            .ok_or_else(|| ParsingError::from_kind(parsed.at, ErrorKind::InvalidUtf8))?;
+
+        Ok(Span::ate(parsed.at, extract.to_string()))
     }
 }
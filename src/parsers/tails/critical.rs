@@ -2,27 +2,53 @@ use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Suggestion};
 
 /// See [`critical`](`crate::ParserConstUtils::critical`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Critical<T, P: Parser<T>> {
+pub struct Critical<
+    T,
+    P: Parser<T, I>,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone = &'static str,
+> {
     parser: P,
-    message: Option<&'static str>,
+    message: Option<M>,
+    code: Option<&'static str>,
+    suggest: Option<fn(&ParsingError) -> Option<Suggestion>>,
     unexpected_eof_msg: bool,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Critical<T, P> {
-    pub const fn new(parser: P, message: Option<&'static str>) -> Self {
+impl<T, P: Parser<T, I>, I: InputKind, M: Into<Cow<'static, str>> + Clone> Critical<T, P, I, M> {
+    pub const fn new(parser: P, message: Option<M>) -> Self {
         Self {
             parser,
             message,
+            code: None,
+            suggest: None,
             unexpected_eof_msg: true,
             _p: PhantomData,
         }
     }
 
+    /// Attach a code to the error (see [`crate::ParsingError::with_code`])
+    pub const fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Suggest an edit fixing the error (see [`crate::ParsingError::with_suggestion`])
+    ///
+    /// The provided function is called with the error when the parser fails
+    pub const fn with_suggestion(
+        mut self,
+        suggest: fn(&ParsingError) -> Option<Suggestion>,
+    ) -> Self {
+        self.suggest = Some(suggest);
+        self
+    }
+
     /// Return a specific error message if the parser fails
     /// because it reached the end of the input
     pub const fn unexpected_eof_msg(mut self, enable: bool) -> Self {
@@ -31,24 +57,38 @@ impl<T, P: Parser<T>> Critical<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<T> for Critical<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
-        let is_empty = input.inner().is_empty();
+impl<T, P: Parser<T, I>, I: InputKind, M: Into<Cow<'static, str>> + Clone> Parser<T, I>
+    for Critical<T, P, I, M>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let is_empty = input.is_empty();
 
         self.parser.parse(input).map_err(|err| {
-            let message = if is_empty && self.unexpected_eof_msg {
-                Cow::Borrowed("unexpected end of input")
+            // Errors that are already critical are propagated as they are
+            if err.is_critical() {
+                return err;
+            }
+
+            let err = if is_empty && self.unexpected_eof_msg {
+                err.criticalize_kind(ErrorKind::UnexpectedEndOfInput)
+            } else if let Some(message) = &self.message {
+                err.criticalize(message.clone())
+            } else if let Some(message) = err.atomic_error().map(str::to_owned) {
+                err.criticalize(message)
             } else {
-                match self.message {
-                    Some(message) => Cow::Borrowed(message),
-                    None => match err.atomic_error() {
-                        Some(message) => Cow::Borrowed(message),
-                        None => Cow::Owned(format!("{}", err.inner().expected())),
-                    },
-                }
+                // Formatted when displayed, so it can be customized
+                err.criticalize_auto()
+            };
+
+            let err = match self.code {
+                Some(code) => err.with_code(code),
+                None => err,
             };
 
-            err.criticalize(message)
+            match self.suggest.and_then(|suggest| suggest(&err)) {
+                Some(suggestion) => err.with_suggestion(suggestion),
+                None => err,
+            }
         })
     }
 }
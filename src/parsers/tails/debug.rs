@@ -2,17 +2,19 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{ParserInput, ParserNonConstUtils, ParserResult, parser::Parser};
+use crate::{InputKind, ParserInput, ParserResult, TextInput, parser::Parser};
 
 /// See [`debug`](`crate::ParserConstUtils::debug`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Debugging<T, P: Parser<T>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)> {
+pub struct Debugging<T, P: Parser<T, I>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T, I>), I: InputKind> {
     parser: P,
     debugger: F,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)> Debugging<T, P, F> {
+impl<T, P: Parser<T, I>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T, I>), I: InputKind>
+    Debugging<T, P, F, I>
+{
     pub const fn new(parser: P, debugger: F) -> Self {
         Self {
             parser,
@@ -22,8 +24,10 @@ impl<T, P: Parser<T>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)> Debugging<T, P, F
     }
 }
 
-impl<T, P: Parser<T>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)> Parser<T> for Debugging<T, P, F> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T, I>), I: InputKind> Parser<T, I>
+    for Debugging<T, P, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         (self.debugger)(DebugType::Input(input));
 
         let result = self.parser.parse(input);
@@ -34,8 +38,8 @@ impl<T, P: Parser<T>, F: for<'a, 'b> Fn(DebugType<'a, 'b, T>)> Parser<T> for Deb
     }
 }
 
-#[derive(Debug)]
-pub enum DebugType<'a, 'b, T> {
-    Input(&'a ParserInput<'b>),
+#[perfect_derive(Debug)]
+pub enum DebugType<'a, 'b, T, I: InputKind = TextInput> {
+    Input(&'a ParserInput<'b, I>),
     Result(&'a ParserResult<T>),
 }
@@ -2,23 +2,30 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, containers::Container};
+use crate::{InputKind, Parser, ParserInput, ParserResult, containers::Container};
 
 /// See [`flattened`](`crate::ParserConstUtils::flattened`)
 #[perfect_derive(Debug, Clone, Copy)]
 pub struct Flattened<
     T,
     S: IntoIterator<Item = T>,
-    I: IntoIterator<Item = S>,
-    P: Parser<I>,
+    N: IntoIterator<Item = S>,
+    P: Parser<N, I>,
     C: Container<T>,
+    I: InputKind,
 > {
     parser: P,
-    _p: PhantomData<(I, T, C)>,
+    _p: PhantomData<(N, T, C, I)>,
 }
 
-impl<T, S: IntoIterator<Item = T>, I: IntoIterator<Item = S>, P: Parser<I>, C: Container<T>>
-    Flattened<T, S, I, P, C>
+impl<
+    T,
+    S: IntoIterator<Item = T>,
+    N: IntoIterator<Item = S>,
+    P: Parser<N, I>,
+    C: Container<T>,
+    I: InputKind,
+> Flattened<T, S, N, P, C, I>
 {
     pub const fn new(parser: P) -> Self {
         Self {
@@ -28,10 +35,16 @@ impl<T, S: IntoIterator<Item = T>, I: IntoIterator<Item = S>, P: Parser<I>, C: C
     }
 }
 
-impl<T, S: IntoIterator<Item = T>, I: IntoIterator<Item = S>, P: Parser<I>, C: Container<T>>
-    Parser<C> for Flattened<T, S, I, P, C>
+impl<
+    T,
+    S: IntoIterator<Item = T>,
+    N: IntoIterator<Item = S>,
+    P: Parser<N, I>,
+    C: Container<T>,
+    I: InputKind,
+> Parser<C, I> for Flattened<T, S, N, P, C, I>
 {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<C> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<C> {
         let parsed = self.parser.parse(input)?;
         Ok(parsed.map(|data| C::from_iter(data.into_iter().flatten())))
     }
@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`full`](`crate::ParserConstUtils::full`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Full<T, P: Parser<T>> {
+pub struct Full<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Full<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> Full<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,27 +20,31 @@ impl<T, P: Parser<T>> Full<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<T> for Full<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<T, I> for Full<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         if input.offset() > 0 {
-            return Err(ParsingError::custom(
+            return Err(ParsingError::from_kind(
                 input.at().range(0),
-                "Expected start of input",
+                ErrorKind::StartOfInput,
             ));
         }
 
         let data = self.parser.parse(input)?;
 
-        if data.at.len < input.original().len() {
-            let next_char = input.original()[data.at.len..].chars().next().unwrap();
+        if !input.is_empty() {
+            let found = Found::next_at(input);
 
-            return Err(ParsingError::custom(
-                data.at.start.add(data.at.len).range(next_char.len_utf8()),
-                "Unexpected symbol",
-            ));
+            let len = match found {
+                Found::Char(c) => c.len_utf8(),
+                _ => 1,
+            };
+
+            return Err(
+                ParsingError::from_kind(input.range(len), ErrorKind::EndOfInput).with_found(found),
+            );
         }
 
-        assert!(data.at.len == input.original().len());
+        assert!(data.at.len == input.offset());
 
         Ok(data)
     }
@@ -0,0 +1,43 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use perfect_derive::perfect_derive;
+
+use crate::{ContextFrame, InputKind, Parser, ParserInput, ParserResult};
+
+/// See [`labelled`](`crate::ParserConstUtils::labelled`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct Labelled<
+    T,
+    P: Parser<T, I>,
+    I: InputKind,
+    L: Into<Cow<'static, str>> + Clone = &'static str,
+> {
+    parser: P,
+    label: L,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I>, I: InputKind, L: Into<Cow<'static, str>> + Clone> Labelled<T, P, I, L> {
+    pub const fn new(parser: P, label: L) -> Self {
+        Self {
+            parser,
+            label,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I>, I: InputKind, L: Into<Cow<'static, str>> + Clone> Parser<T, I>
+    for Labelled<T, P, I, L>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let start = input.at();
+
+        self.parser.parse(input).map_err(|err| {
+            err.with_context_frame(ContextFrame {
+                label: self.label.clone().into(),
+                at: start,
+            })
+        })
+    }
+}
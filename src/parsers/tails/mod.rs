@@ -4,15 +4,20 @@ mod critical;
 mod debug;
 mod flattened;
 mod full;
+mod labelled;
 mod or_not;
+mod recover_with;
 mod repeated;
 mod silenced;
 mod spanned;
 mod static_ref;
 mod to;
+mod update_state;
 mod validate;
 mod validate_or_critical;
 mod validate_or_dynamic_critical;
+mod validate_with_state;
+mod warn_if;
 
 pub use self::{
     atomic_err::AtomicErr,
@@ -21,13 +26,18 @@ pub use self::{
     debug::{DebugType, Debugging},
     flattened::Flattened,
     full::Full,
+    labelled::Labelled,
     or_not::OrNot,
+    recover_with::RecoverWith,
     repeated::Repeated,
     silenced::Silenced,
     spanned::Spanned,
     static_ref::StaticRef,
     to::To,
+    update_state::UpdateState,
     validate::Validate,
     validate_or_critical::ValidateOrCriticalMsg,
     validate_or_dynamic_critical::ValidateOrDynamicCriticalMsg,
+    validate_with_state::ValidateWithState,
+    warn_if::WarnIf,
 };
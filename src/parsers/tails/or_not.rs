@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, Span};
 
 /// See [`or_not`](`crate::ParserConstUtils::or_not`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct OrNot<T, P: Parser<T>> {
+pub struct OrNot<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> OrNot<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> OrNot<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,11 +20,11 @@ impl<T, P: Parser<T>> OrNot<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<Option<T>> for OrNot<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<Option<T>> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<Option<T>, I> for OrNot<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<Option<T>> {
         let start = input.at();
 
-        match self.parser.parse(input) {
+        match input.rollback_on_failure(|input| self.parser.parse(input)) {
             Ok(span) => Ok(span.map(Some)),
             Err(err) if err.is_critical() => Err(err),
             Err(_) => Ok(Span::ate(start.range(0), None)),
@@ -0,0 +1,56 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{InputKind, Parser, ParserInput, ParserResult, parsers::RecoveryStrategy};
+
+/// See [`recover_with`](`crate::ParserConstUtils::recover_with`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct RecoverWith<T, P: Parser<T, I>, S: RecoveryStrategy<T, I>, I: InputKind> {
+    parser: P,
+    strategy: S,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I>, S: RecoveryStrategy<T, I>, I: InputKind> RecoverWith<T, P, S, I> {
+    pub const fn new(parser: P, strategy: S) -> Self {
+        Self {
+            parser,
+            strategy,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I>, S: RecoveryStrategy<T, I>, I: InputKind> Parser<T, I>
+    for RecoverWith<T, P, S, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let session = input.session();
+
+        if !session.recovery_enabled() {
+            return self.parser.parse(input);
+        }
+
+        let checkpoint = session.checkpoint();
+
+        let err = match self.parser.parse(input) {
+            Ok(span) => return Ok(span),
+            Err(err) if !err.is_critical() => return Err(err),
+            Err(err) => err,
+        };
+
+        // Critical errors are not rolled back by the parser itself,
+        // so discard what it recorded before recovering
+        session.rollback(checkpoint);
+
+        match self.strategy.recover(input, &err) {
+            Some(recovered) => {
+                session.record_recovered_error(err);
+                Ok(recovered)
+            }
+
+            None => Err(err),
+        }
+    }
+}
@@ -3,21 +3,21 @@ use std::marker::PhantomData;
 use perfect_derive::perfect_derive;
 
 use crate::{
-    Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError, Span,
+    ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
     containers::Container,
 };
 
 /// See [`repeated`](`crate::ParserConstUtils::repeated`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Repeated<T, P: Parser<T>, C: Container<T>> {
+pub struct Repeated<T, P: Parser<T, I>, C: Container<T>, I: InputKind> {
     parser: P,
     min: Option<usize>,
     max: Option<usize>,
     exactly: Option<usize>,
-    _p: PhantomData<(T, C)>,
+    _p: PhantomData<(T, C, I)>,
 }
 
-impl<T, P: Parser<T>, C: Container<T>> Repeated<T, P, C> {
+impl<T, P: Parser<T, I>, C: Container<T>, I: InputKind> Repeated<T, P, C, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -87,8 +87,8 @@ impl<T, P: Parser<T>, C: Container<T>> Repeated<T, P, C> {
     }
 }
 
-impl<T, P: Parser<T>, C: Container<T>> Parser<C> for Repeated<T, P, C> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<C> {
+impl<T, P: Parser<T, I>, C: Container<T>, I: InputKind> Parser<C, I> for Repeated<T, P, C, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<C> {
         let start = input.at();
         let mut ate = 0;
 
@@ -96,7 +96,7 @@ impl<T, P: Parser<T>, C: Container<T>> Parser<C> for Repeated<T, P, C> {
         let mut count = 0;
 
         let err = loop {
-            match self.parser.parse(input) {
+            match input.rollback_on_failure(|input| self.parser.parse(input)) {
                 Err(err) if err.is_critical() => return Err(err),
                 Err(err) => break Some(err),
                 Ok(span) => {
@@ -116,6 +116,11 @@ impl<T, P: Parser<T>, C: Container<T>> Parser<C> for Repeated<T, P, C> {
                     {
                         break None;
                     }
+
+                    // A repetition that consumed nothing would match the same way forever
+                    if span.at.len == 0 && count >= self.min.or(self.exactly).unwrap_or(0) {
+                        break None;
+                    }
                 }
             }
         };
@@ -124,7 +129,10 @@ impl<T, P: Parser<T>, C: Container<T>> Parser<C> for Repeated<T, P, C> {
             && count < min
         {
             return Err(err.filter(|_| count == 0).unwrap_or_else(|| {
-                ParsingError::custom(input.at().range(ate), "Not enough repetitions")
+                ParsingError::from_kind(
+                    input.at().range(ate),
+                    ErrorKind::NotEnoughRepetitions { min, found: count },
+                )
             }));
         }
 
@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`silenced`](`crate::ParserConstUtils::silenced`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Silenced<T, P: Parser<T>> {
+pub struct Silenced<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Silenced<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> Silenced<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,8 +20,8 @@ impl<T, P: Parser<T>> Silenced<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<()> for Silenced<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<(), I> for Silenced<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
         self.parser.parse(input).map(|input| input.forge_here(()))
     }
 }
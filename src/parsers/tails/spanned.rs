@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, Span};
+use crate::{InputKind, Parser, ParserInput, ParserResult, Span};
 
 /// See [`spanned`](`crate::ParserConstUtils::spanned`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Spanned<T, P: Parser<T>> {
+pub struct Spanned<T, P: Parser<T, I>, I: InputKind> {
     parser: P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> Spanned<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> Spanned<T, P, I> {
     pub const fn new(parser: P) -> Self {
         Self {
             parser,
@@ -20,8 +20,8 @@ impl<T, P: Parser<T>> Spanned<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<Span<T>> for Spanned<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<Span<T>> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<Span<T>, I> for Spanned<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<Span<T>> {
         let parsed = self.parser.parse(input)?;
         Ok(Span::ate(parsed.at, parsed))
     }
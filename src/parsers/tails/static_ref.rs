@@ -2,16 +2,16 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`static_ref`](`crate::ParserConstUtils::static_ref`)
 #[perfect_derive(Clone, Copy)]
-pub struct StaticRef<T, P: Parser<T> + 'static> {
+pub struct StaticRef<T, P: Parser<T, I> + 'static, I: InputKind> {
     parser: &'static P,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>> StaticRef<T, P> {
+impl<T, P: Parser<T, I>, I: InputKind> StaticRef<T, P, I> {
     pub const fn new(parser: &'static P) -> Self {
         Self {
             parser,
@@ -20,8 +20,8 @@ impl<T, P: Parser<T>> StaticRef<T, P> {
     }
 }
 
-impl<T, P: Parser<T>> Parser<T> for StaticRef<T, P> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, I: InputKind> Parser<T, I> for StaticRef<T, P, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.parser.parse(input)
     }
 }
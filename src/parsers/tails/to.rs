@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`to`](`crate::ParserConstUtils::to`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct To<T, P: Parser<T>, U: Copy> {
+pub struct To<T, P: Parser<T, I>, U: Copy, I: InputKind> {
     parser: P,
     data: U,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>, U: Copy> To<T, P, U> {
+impl<T, P: Parser<T, I>, U: Copy, I: InputKind> To<T, P, U, I> {
     pub const fn new(parser: P, data: U) -> Self {
         Self {
             parser,
@@ -22,8 +22,8 @@ impl<T, P: Parser<T>, U: Copy> To<T, P, U> {
     }
 }
 
-impl<T, P: Parser<T>, U: Copy> Parser<U> for To<T, P, U> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<U> {
+impl<T, P: Parser<T, I>, U: Copy, I: InputKind> Parser<U, I> for To<T, P, U, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<U> {
         self.parser
             .parse(input)
             .map(|span| span.forge_here(self.data))
@@ -0,0 +1,35 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{InputKind, Parser, ParserInput, ParserResult};
+
+/// See [`update_state`](`crate::ParserConstUtils::update_state`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct UpdateState<T, P: Parser<T, I>, F: Fn(&T, &mut I::State), I: InputKind> {
+    parser: P,
+    updater: F,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T, &mut I::State), I: InputKind> UpdateState<T, P, F, I> {
+    pub const fn new(parser: P, updater: F) -> Self {
+        Self {
+            parser,
+            updater,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T, &mut I::State), I: InputKind> Parser<T, I>
+    for UpdateState<T, P, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let parsed = self.parser.parse(input)?;
+
+        input.update_state(|state| (self.updater)(&parsed.data, state));
+
+        Ok(parsed)
+    }
+}
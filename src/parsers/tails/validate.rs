@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`validate`](`crate::ParserConstUtils::validate`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct Validate<T, P: Parser<T>, F: Fn(&T) -> bool> {
+pub struct Validate<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind> {
     parser: P,
     validator: F,
-    _p: PhantomData<T>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> bool> Validate<T, P, F> {
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind> Validate<T, P, F, I> {
     pub const fn new(parser: P, validator: F) -> Self {
         Self {
             parser,
@@ -22,17 +22,17 @@ impl<T, P: Parser<T>, F: Fn(&T) -> bool> Validate<T, P, F> {
     }
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> bool> Parser<T> for Validate<T, P, F> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind> Parser<T, I> for Validate<T, P, F, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         let start = input.at();
         let parsed = self.parser.parse(input)?;
 
         if (self.validator)(&parsed.data) {
             Ok(parsed)
         } else {
-            Err(ParsingError::custom(
+            Err(ParsingError::from_kind(
                 start.range(parsed.at.len),
-                "validator failed",
+                ErrorKind::ValidationFailed,
             ))
         }
     }
@@ -1,20 +1,28 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`validate_or_critical`](`crate::ParserConstUtils::validate_or_critical`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct ValidateOrCriticalMsg<T, P: Parser<T>, F: Fn(&T) -> bool> {
+pub struct ValidateOrCriticalMsg<
+    T,
+    P: Parser<T, I>,
+    F: Fn(&T) -> bool,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone = &'static str,
+> {
     parser: P,
     validator: F,
-    message: &'static str,
-    _p: PhantomData<T>,
+    message: M,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> bool> ValidateOrCriticalMsg<T, P, F> {
-    pub const fn new(parser: P, validator: F, message: &'static str) -> Self {
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind, M: Into<Cow<'static, str>> + Clone>
+    ValidateOrCriticalMsg<T, P, F, I, M>
+{
+    pub const fn new(parser: P, validator: F, message: M) -> Self {
         Self {
             parser,
             validator,
@@ -24,8 +32,10 @@ impl<T, P: Parser<T>, F: Fn(&T) -> bool> ValidateOrCriticalMsg<T, P, F> {
     }
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> bool> Parser<T> for ValidateOrCriticalMsg<T, P, F> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind, M: Into<Cow<'static, str>> + Clone>
+    Parser<T, I> for ValidateOrCriticalMsg<T, P, F, I, M>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         let start = input.at();
         let parsed = self.parser.parse(input)?;
 
@@ -33,8 +43,8 @@ impl<T, P: Parser<T>, F: Fn(&T) -> bool> Parser<T> for ValidateOrCriticalMsg<T,
             Ok(parsed)
         } else {
             Err(
-                ParsingError::custom(start.range(parsed.at.len), "Validator failed")
-                    .criticalize(self.message),
+                ParsingError::from_kind(start.range(parsed.at.len), ErrorKind::ValidationFailed)
+                    .criticalize(self.message.clone()),
             )
         }
     }
@@ -2,43 +2,70 @@ use std::{borrow::Cow, marker::PhantomData};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult, ParsingError};
+use crate::{
+    ErrorKind, InputKind, InputRange, Parser, ParserInput, ParserResult, ParsingError, Suggestion,
+};
 
 /// See [`validate_or_dynamic_critical`](`crate::ParserConstUtils::validate_or_dynamic_critical`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct ValidateOrDynamicCriticalMsg<T, P: Parser<T>, F: Fn(&T) -> Result<(), Cow<'static, str>>>
-{
+pub struct ValidateOrDynamicCriticalMsg<
+    T,
+    P: Parser<T, I>,
+    F: Fn(&T) -> Result<(), Cow<'static, str>>,
+    I: InputKind,
+> {
     parser: P,
     validator: F,
-    _p: PhantomData<T>,
+    suggest: Option<fn(&T, InputRange) -> Option<Suggestion>>,
+    _p: PhantomData<(T, I)>,
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> Result<(), Cow<'static, str>>>
-    ValidateOrDynamicCriticalMsg<T, P, F>
+impl<T, P: Parser<T, I>, F: Fn(&T) -> Result<(), Cow<'static, str>>, I: InputKind>
+    ValidateOrDynamicCriticalMsg<T, P, F, I>
 {
     pub const fn new(parser: P, validator: F) -> Self {
         Self {
             parser,
             validator,
+            suggest: None,
             _p: PhantomData,
         }
     }
+
+    /// Suggest an edit fixing the error (see [`crate::ParsingError::with_suggestion`])
+    ///
+    /// The provided function is called with the rejected value and its location
+    /// when the validation fails
+    pub const fn with_suggestion(
+        mut self,
+        suggest: fn(&T, InputRange) -> Option<Suggestion>,
+    ) -> Self {
+        self.suggest = Some(suggest);
+        self
+    }
 }
 
-impl<T, P: Parser<T>, F: Fn(&T) -> Result<(), Cow<'static, str>>> Parser<T>
-    for ValidateOrDynamicCriticalMsg<T, P, F>
+impl<T, P: Parser<T, I>, F: Fn(&T) -> Result<(), Cow<'static, str>>, I: InputKind> Parser<T, I>
+    for ValidateOrDynamicCriticalMsg<T, P, F, I>
 {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         let start = input.at();
         let parsed = self.parser.parse(input)?;
 
         match (self.validator)(&parsed.data) {
             Ok(()) => Ok(parsed),
 
-            Err(msg) => Err(
-                ParsingError::custom(start.range(parsed.at.len), "Validator failed")
-                    .criticalize(msg),
-            ),
+            Err(msg) => {
+                let at = start.range(parsed.at.len);
+                let err = ParsingError::from_kind(at, ErrorKind::ValidationFailed).criticalize(msg);
+
+                Err(
+                    match self.suggest.and_then(|suggest| suggest(&parsed.data, at)) {
+                        Some(suggestion) => err.with_suggestion(suggestion),
+                        None => err,
+                    },
+                )
+            }
         }
     }
 }
@@ -0,0 +1,41 @@
+use std::marker::PhantomData;
+
+use perfect_derive::perfect_derive;
+
+use crate::{ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError};
+
+/// See [`validate_with_state`](`crate::ParserConstUtils::validate_with_state`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct ValidateWithState<T, P: Parser<T, I>, F: Fn(&T, &I::State) -> bool, I: InputKind> {
+    parser: P,
+    validator: F,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T, &I::State) -> bool, I: InputKind> ValidateWithState<T, P, F, I> {
+    pub const fn new(parser: P, validator: F) -> Self {
+        Self {
+            parser,
+            validator,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T, &I::State) -> bool, I: InputKind> Parser<T, I>
+    for ValidateWithState<T, P, F, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let start = input.at();
+        let parsed = self.parser.parse(input)?;
+
+        if input.state(|state| (self.validator)(&parsed.data, state)) {
+            Ok(parsed)
+        } else {
+            Err(ParsingError::from_kind(
+                start.range(parsed.at.len),
+                ErrorKind::ValidationFailed,
+            ))
+        }
+    }
+}
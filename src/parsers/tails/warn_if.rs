@@ -0,0 +1,47 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use perfect_derive::perfect_derive;
+
+use crate::{Diagnostic, InputKind, Parser, ParserInput, ParserResult};
+
+/// See [`warn_if`](`crate::ParserConstUtils::warn_if`)
+#[perfect_derive(Debug, Clone, Copy)]
+pub struct WarnIf<
+    T,
+    P: Parser<T, I>,
+    F: Fn(&T) -> bool,
+    I: InputKind,
+    M: Into<Cow<'static, str>> + Clone = &'static str,
+> {
+    parser: P,
+    predicate: F,
+    message: M,
+    _p: PhantomData<(T, I)>,
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind, M: Into<Cow<'static, str>> + Clone>
+    WarnIf<T, P, F, I, M>
+{
+    pub const fn new(parser: P, predicate: F, message: M) -> Self {
+        Self {
+            parser,
+            predicate,
+            message,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Parser<T, I>, F: Fn(&T) -> bool, I: InputKind, M: Into<Cow<'static, str>> + Clone>
+    Parser<T, I> for WarnIf<T, P, F, I, M>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let parsed = self.parser.parse(input)?;
+
+        if (self.predicate)(&parsed.data) {
+            input.emit_diagnostic(Diagnostic::warning(parsed.at, self.message.clone()));
+        }
+
+        Ok(parsed)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{ByteSource, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`char`](`crate::parsers::helpers::char`)
 #[derive(Clone, Copy)]
@@ -12,21 +12,21 @@ impl Char {
     }
 }
 
-impl Parser<char> for Char {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<char> {
+impl<I: InputKind<Source: ByteSource>> Parser<char, I> for Char {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<char> {
         let start = input.at();
 
-        let span = input
-            .try_eat_char()
-            .ok_or_else(|| ParsingError::expected_char(start.range(0), self.char))?;
+        let span = input.try_eat_char().ok_or_else(|| {
+            ParsingError::expected_char(start.range(0), self.char).with_found(Found::next_at(input))
+        })?;
 
         if span.data == self.char {
             Ok(span)
         } else {
-            Err(ParsingError::expected_char(
-                start.range(span.data.len_utf8()),
-                self.char,
-            ))
+            Err(
+                ParsingError::expected_char(start.range(span.data.len_utf8()), self.char)
+                    .with_found(Found::Char(span.data)),
+            )
         }
     }
 }
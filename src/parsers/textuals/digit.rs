@@ -1,4 +1,6 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
 
 /// See [`digit`](`crate::parsers::helpers::digit`)
 #[derive(Clone, Copy)]
@@ -12,21 +14,23 @@ impl Digit {
     }
 }
 
-impl Parser<()> for Digit {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+impl<I: InputKind<Source: ByteSource>> Parser<(), I> for Digit {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
         let start = input.at();
 
-        let c = input
-            .try_eat_char()
-            .ok_or_else(|| ParsingError::custom(start.range(0), "No character left"))?;
+        let c = input.try_eat_char().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::Digit { radix: self.radix })
+                .with_found(Found::next_at(input))
+        })?;
 
         if c.data.is_digit(self.radix) {
             Ok(c.forge_here(()))
         } else {
-            Err(ParsingError::custom(
+            Err(ParsingError::from_kind(
                 start.range(c.data.len_utf8()),
-                "Character filter failed",
-            ))
+                ErrorKind::Digit { radix: self.radix },
+            )
+            .with_found(Found::Char(c.data)))
         }
     }
 }
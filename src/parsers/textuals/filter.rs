@@ -1,6 +1,8 @@
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
 
 /// See [`filter`](`crate::parsers::helpers::filter`)
 #[perfect_derive(Clone, Copy)]
@@ -14,21 +16,22 @@ impl<F: Fn(char) -> bool> Filter<F> {
     }
 }
 
-impl<F: Fn(char) -> bool> Parser<char> for Filter<F> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<char> {
+impl<F: Fn(char) -> bool, I: InputKind<Source: ByteSource>> Parser<char, I> for Filter<F> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<char> {
         let start = input.at();
 
-        let c = input
-            .try_eat_char()
-            .ok_or_else(|| ParsingError::custom(start.range(0), "No character left"))?;
+        let c = input.try_eat_char().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::CharFilter)
+                .with_found(Found::next_at(input))
+        })?;
 
         if (self.func)(c.data) {
             Ok(c)
         } else {
-            Err(ParsingError::custom(
-                start.range(c.data.len_utf8()),
-                "Character filter failed",
-            ))
+            Err(
+                ParsingError::from_kind(start.range(c.data.len_utf8()), ErrorKind::CharFilter)
+                    .with_found(Found::Char(c.data)),
+            )
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{ByteSource, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError};
 
 /// See [`just`](`crate::parsers::helpers::just`)
 #[derive(Clone, Copy)]
@@ -12,15 +12,19 @@ impl Just {
     }
 }
 
-impl Parser<&'static str> for Just {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<&'static str> {
+impl<I: InputKind<Source: ByteSource>> Parser<&'static str, I> for Just {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<&'static str> {
+        let before = *input;
         let start = input.at();
 
         let span = input
             // Try to eat the string
             .try_eat(self.str.len())
             // Otherwise, generate an error
-            .ok_or_else(|| ParsingError::expected_str(start.range(0), self.str))?;
+            .ok_or_else(|| {
+                ParsingError::expected_str(start.range(0), self.str)
+                    .with_found(Found::next_token_at(&before))
+            })?;
 
         // Ensure it was correctly parsed
         if span.data == self.str {
@@ -28,10 +32,10 @@ impl Parser<&'static str> for Just {
             Ok(span.forge_here(self.str))
         } else {
             // Otherwise, generate an error
-            Err(ParsingError::expected_str(
-                start.range(span.at.len),
-                self.str,
-            ))
+            Err(
+                ParsingError::expected_str(start.range(span.at.len), self.str)
+                    .with_found(Found::next_token_at(&before)),
+            )
         }
     }
 }
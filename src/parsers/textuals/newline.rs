@@ -1,4 +1,6 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError, Span};
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
+};
 
 /// See [`newline`](`crate::parsers::helpers::newline`)
 #[derive(Clone, Copy)]
@@ -16,19 +18,19 @@ impl Default for Newline {
     }
 }
 
-impl Parser<()> for Newline {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
-        let input_str = input.inner();
+impl<I: InputKind<Source: ByteSource>> Parser<(), I> for Newline {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
+        let bytes = input.remaining_bytes();
 
-        let trimmed = if input_str.starts_with("\r\n") {
+        let trimmed = if bytes.starts_with(b"\r\n") {
             2
-        } else if input_str.starts_with('\r') || input_str.starts_with('\n') {
+        } else if bytes.starts_with(b"\r") || bytes.starts_with(b"\n") {
             1
         } else {
-            return Err(ParsingError::custom(
-                input.at().range(0),
-                "Expected at least one newline",
-            ));
+            return Err(
+                ParsingError::from_kind(input.at().range(0), ErrorKind::Newline)
+                    .with_found(Found::next_at(input)),
+            );
         };
 
         Ok(Span::ate(input.range(trimmed), ()))
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
 
 /// See [`one_of_chars`](`crate::parsers::helpers::one_of_chars`)
 pub struct OneOfChars {
@@ -13,21 +15,22 @@ impl OneOfChars {
     }
 }
 
-impl Parser<char> for OneOfChars {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<char> {
+impl<I: InputKind<Source: ByteSource>> Parser<char, I> for OneOfChars {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<char> {
         let start = input.at();
 
-        let span = input
-            .try_eat_char()
-            .ok_or_else(|| ParsingError::custom(start.range(0), "expected a character to match"))?;
+        let span = input.try_eat_char().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::CharInSet)
+                .with_found(Found::next_at(input))
+        })?;
 
         if self.set.contains(&span.data) {
             Ok(span)
         } else {
-            Err(ParsingError::custom(
-                start.range(span.data.len_utf8()),
-                "character did not match against the provided set",
-            ))
+            Err(
+                ParsingError::from_kind(start.range(span.data.len_utf8()), ErrorKind::CharInSet)
+                    .with_found(Found::Char(span.data)),
+            )
         }
     }
 }
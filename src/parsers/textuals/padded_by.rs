@@ -2,17 +2,17 @@ use std::marker::PhantomData;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult};
 
 /// See [`padded_by`](`crate::ParserConstUtils::padded_by`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct PaddedBy<T, TP: Parser<T>, P, PP: Parser<P>> {
+pub struct PaddedBy<T, TP: Parser<T, I>, P, PP: Parser<P, I>, I: InputKind> {
     middle: TP,
     padding: PP,
-    _p: PhantomData<(T, P)>,
+    _p: PhantomData<(T, P, I)>,
 }
 
-impl<T, TP: Parser<T>, P, PP: Parser<P>> PaddedBy<T, TP, P, PP> {
+impl<T, TP: Parser<T, I>, P, PP: Parser<P, I>, I: InputKind> PaddedBy<T, TP, P, PP, I> {
     pub const fn new(middle: TP, padding: PP) -> Self {
         Self {
             middle,
@@ -22,8 +22,10 @@ impl<T, TP: Parser<T>, P, PP: Parser<P>> PaddedBy<T, TP, P, PP> {
     }
 }
 
-impl<T, TP: Parser<T>, P, PP: Parser<P>> Parser<T> for PaddedBy<T, TP, P, PP> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, TP: Parser<T, I>, P, PP: Parser<P, I>, I: InputKind> Parser<T, I>
+    for PaddedBy<T, TP, P, PP, I>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         let start = self.padding.parse(input)?;
         let middle = self.middle.parse(input)?;
         let end = self.padding.parse(input)?;
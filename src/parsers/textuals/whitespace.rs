@@ -1,4 +1,6 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError};
+use crate::{
+    ByteSource, ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError,
+};
 
 /// See [`whitespace`](`crate::parsers::helpers::whitespace`)
 #[derive(Clone, Copy)]
@@ -24,21 +26,22 @@ impl Default for Whitespace {
     }
 }
 
-impl Parser<()> for Whitespace {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
+impl<I: InputKind<Source: ByteSource>> Parser<(), I> for Whitespace {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
         let start = input.at();
 
-        let c = input
-            .try_eat_char()
-            .ok_or_else(|| ParsingError::custom(start.range(0), "No character left"))?;
+        let c = input.try_eat_char().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::Whitespace)
+                .with_found(Found::next_at(input))
+        })?;
 
         if c.data.is_whitespace() {
             Ok(c.forge_here(()))
         } else {
-            Err(ParsingError::custom(
-                start.range(c.data.len_utf8()),
-                "Character filter failed",
-            ))
+            Err(
+                ParsingError::from_kind(start.range(c.data.len_utf8()), ErrorKind::Whitespace)
+                    .with_found(Found::Char(c.data)),
+            )
         }
     }
 }
@@ -1,4 +1,6 @@
-use crate::{Parser, ParserInput, ParserResult, ParsingError, Span};
+use crate::{
+    ByteSource, ErrorKind, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span,
+};
 
 /// See [`whitespaces`](`crate::parsers::helpers::whitespaces`)
 #[derive(Clone, Copy)]
@@ -34,25 +36,36 @@ impl Default for Whitespaces {
     }
 }
 
-impl Parser<()> for Whitespaces {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<()> {
-        let input_str = input.inner();
+impl<I: InputKind<Source: ByteSource>> Parser<(), I> for Whitespaces {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<()> {
+        let start = input.at();
 
-        let trimmed = if self.no_newline {
-            input_str.trim_start_matches(|c: char| c.is_whitespace() && c != '\n' && c != '\r')
-        } else {
-            input_str.trim_start()
-        };
+        // Decode characters one by one, as getting the whole remaining content
+        // requires validating it for binary inputs
+        loop {
+            let mut next = *input;
+
+            match next.try_eat_char() {
+                Some(c)
+                    if c.data.is_whitespace()
+                        && !(self.no_newline && (c.data == '\n' || c.data == '\r')) =>
+                {
+                    *input = next;
+                }
+
+                _ => break,
+            }
+        }
 
-        let trimmed = input_str.len() - trimmed.len();
+        let trimmed = input.offset() - start.offset();
 
         if self.at_least_one && trimmed == 0 {
-            Err(ParsingError::custom(
-                input.at().range(0),
-                "Expected at least one whitespace",
+            Err(ParsingError::from_kind(
+                start.range(0),
+                ErrorKind::Whitespaces,
             ))
         } else {
-            Ok(Span::ate(input.range(trimmed), ()))
+            Ok(Span::ate(start.range(trimmed), ()))
         }
     }
 }
@@ -2,16 +2,16 @@ use std::sync::LazyLock;
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult, TextInput};
 
 /// See [`lazily_define`](`crate::parsers::helpers::lazily_define`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct LazilyDefined<T> {
-    parser_ref: LazyLock<Box<dyn Parser<T> + Send + Sync>>,
+pub struct LazilyDefined<T, I: InputKind = TextInput> {
+    parser_ref: LazyLock<Box<dyn Parser<T, I> + Send + Sync>>,
 }
 
-impl<T> LazilyDefined<T> {
-    pub const fn new(lazy_define: fn() -> Box<dyn Parser<T> + Send + Sync>) -> Self {
+impl<T, I: InputKind> LazilyDefined<T, I> {
+    pub const fn new(lazy_define: fn() -> Box<dyn Parser<T, I> + Send + Sync>) -> Self {
         Self {
             parser_ref: LazyLock::new(lazy_define),
         }
@@ -22,8 +22,8 @@ impl<T> LazilyDefined<T> {
     }
 }
 
-impl<T> Parser<T> for LazilyDefined<T> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, I: InputKind> Parser<T, I> for LazilyDefined<T, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.parser_ref.parse(input)
     }
 }
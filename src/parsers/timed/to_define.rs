@@ -2,15 +2,18 @@ use std::{cell::RefCell, rc::Rc};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult, TextInput};
+
+/// Parser provided when defining
+type BoxedParser<T, I> = Box<dyn Parser<T, I>>;
 
 /// See [`to_define`](`crate::parsers::helpers::to_define`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct ToDefine<T> {
-    parser_ref: Rc<RefCell<Option<Box<dyn Parser<T>>>>>,
+pub struct ToDefine<T, I: InputKind = TextInput> {
+    parser_ref: Rc<RefCell<Option<BoxedParser<T, I>>>>,
 }
 
-impl<T> ToDefine<T> {
+impl<T, I: InputKind> ToDefine<T, I> {
     pub fn new() -> Self {
         Self {
             parser_ref: Rc::new(RefCell::new(None)),
@@ -20,7 +23,7 @@ impl<T> ToDefine<T> {
     /// Set the parser's final content
     ///
     /// Panics if called twice
-    pub fn define(&self, parser: impl Parser<T> + 'static) {
+    pub fn define(&self, parser: impl Parser<T, I> + 'static) {
         let mut borrowed = self.parser_ref.borrow_mut();
 
         let prev = borrowed.replace(Box::new(parser));
@@ -32,14 +35,14 @@ impl<T> ToDefine<T> {
     }
 }
 
-impl<T> Default for ToDefine<T> {
+impl<T, I: InputKind> Default for ToDefine<T, I> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T> Parser<T> for ToDefine<T> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, I: InputKind> Parser<T, I> for ToDefine<T, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.parser_ref
             .borrow()
             .as_ref()
@@ -2,15 +2,18 @@ use std::sync::{Arc, RwLock};
 
 use perfect_derive::perfect_derive;
 
-use crate::{Parser, ParserInput, ParserNonConstUtils, ParserResult};
+use crate::{InputKind, Parser, ParserInput, ParserResult, TextInput};
+
+/// Parser provided when defining
+type BoxedParser<T, I> = Box<dyn Parser<T, I> + Send + Sync>;
 
 /// See [`to_define_shared`](`crate::parsers::helpers::to_define_shared`)
 #[perfect_derive(Debug, Clone, Copy)]
-pub struct ToDefineShared<T> {
-    parser_ref: Arc<RwLock<Option<Box<dyn Parser<T> + Send + Sync>>>>,
+pub struct ToDefineShared<T, I: InputKind = TextInput> {
+    parser_ref: Arc<RwLock<Option<BoxedParser<T, I>>>>,
 }
 
-impl<T> ToDefineShared<T> {
+impl<T, I: InputKind> ToDefineShared<T, I> {
     pub fn new() -> Self {
         Self {
             parser_ref: Arc::new(RwLock::new(None)),
@@ -20,7 +23,7 @@ impl<T> ToDefineShared<T> {
     /// Set the parser's final content
     ///
     /// Panics if called twice
-    pub fn define(&self, parser: impl Parser<T> + Send + Sync + 'static) {
+    pub fn define(&self, parser: impl Parser<T, I> + Send + Sync + 'static) {
         let mut borrowed = self.parser_ref.write().unwrap();
 
         let prev = borrowed.replace(Box::new(parser));
@@ -32,14 +35,14 @@ impl<T> ToDefineShared<T> {
     }
 }
 
-impl<T> Default for ToDefineShared<T> {
+impl<T, I: InputKind> Default for ToDefineShared<T, I> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T> Parser<T> for ToDefineShared<T> {
-    fn parse_inner(&self, input: &mut ParserInput) -> ParserResult<T> {
+impl<T, I: InputKind> Parser<T, I> for ToDefineShared<T, I> {
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
         self.parser_ref
             .read()
             .unwrap()
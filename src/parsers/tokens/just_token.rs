@@ -0,0 +1,38 @@
+use std::fmt;
+
+use crate::{ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
+
+/// See [`token`](`crate::parsers::helpers::token`)
+#[derive(Debug, Clone, Copy)]
+pub struct JustToken<T: PartialEq + Clone + fmt::Debug> {
+    token: T,
+}
+
+impl<T: PartialEq + Clone + fmt::Debug> JustToken<T> {
+    pub const fn new(token: T) -> Self {
+        Self { token }
+    }
+}
+
+impl<T: PartialEq + Clone + fmt::Debug, I: InputKind<Source = [Span<T>]>> Parser<T, I>
+    for JustToken<T>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<T> {
+        let start = input.at();
+
+        let expected = || ErrorKind::Token {
+            token: format!("{:?}", self.token).into(),
+        };
+
+        let span = input.try_eat_token().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), expected()).with_found(Found::EndOfInput)
+        })?;
+
+        if *span.data == self.token {
+            Ok(span.map(T::clone))
+        } else {
+            Err(ParsingError::from_kind(span.at, expected())
+                .with_found(Found::Token(format!("{:?}", span.data).into())))
+        }
+    }
+}
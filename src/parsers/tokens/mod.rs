@@ -0,0 +1,4 @@
+mod just_token;
+mod select;
+
+pub use self::{just_token::JustToken, select::Select};
@@ -0,0 +1,40 @@
+use std::{fmt, marker::PhantomData};
+
+use perfect_derive::perfect_derive;
+
+use crate::{ErrorKind, Found, InputKind, Parser, ParserInput, ParserResult, ParsingError, Span};
+
+/// See [`select`](`crate::parsers::helpers::select`)
+#[perfect_derive(Clone, Copy)]
+pub struct Select<T: fmt::Debug, O, F: Fn(&T) -> Option<O>> {
+    func: F,
+    _p: PhantomData<(T, O)>,
+}
+
+impl<T: fmt::Debug, O, F: Fn(&T) -> Option<O>> Select<T, O, F> {
+    pub const fn new(func: F) -> Self {
+        Self {
+            func,
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, O, F: Fn(&T) -> Option<O>, I: InputKind<Source = [Span<T>]>> Parser<O, I>
+    for Select<T, O, F>
+{
+    fn parse_inner(&self, input: &mut ParserInput<I>) -> ParserResult<O> {
+        let start = input.at();
+        let found = Found::next_at(input);
+
+        let span = input.try_eat_token().ok_or_else(|| {
+            ParsingError::from_kind(start.range(0), ErrorKind::TokenFilter)
+                .with_found(Found::EndOfInput)
+        })?;
+
+        match (self.func)(span.data) {
+            Some(selected) => Ok(span.forge_here(selected)),
+            None => Err(ParsingError::from_kind(span.at, ErrorKind::TokenFilter).with_found(found)),
+        }
+    }
+}
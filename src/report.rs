@@ -1,8 +1,11 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::LazyCell, ops::Range};
 
-use annotate_snippets::{Level, Renderer, Snippet};
+use annotate_snippets::{AnnotationKind, Group, Level, Origin, Padding, Patch, Renderer, Snippet};
 
-use crate::{InputRange, ParserExpectation, ParsingError};
+use crate::{
+    ContextFrame, DefaultFormatter, Diagnostic, InputRange, LineIndex, Message, MessageFormatter,
+    ParsingError, Severity, SourceMap, SourceMapErr,
+};
 
 /// An error report, that will display a nice, human-readable extract of the
 /// input content along with the error message at the location it happened.
@@ -16,37 +19,330 @@ pub struct ErrorReport<'a, 'b, 'c> {
     /// Path to the source
     source_path: &'b str,
 
+    /// Index of the source's lines, built when displaying the report if not provided
+    line_index: Option<&'a LineIndex>,
+
     /// Error message
-    err_msg: Cow<'c, str>,
+    err_msg: ReportText<'c>,
 
     /// Offset in the source content
     offset: usize,
 
     /// Number of input bytes covered by the error
     len: usize,
+
+    /// Severity of the report
+    severity: Severity,
+
+    /// Error code
+    code: Option<Cow<'c, str>>,
+
+    /// Additional locations related to the error
+    secondary_labels: Vec<ReportLabel<'c>>,
+
+    /// Additional locations related to the error, in other sources
+    external_labels: Vec<ExternalLabel<'a, 'b, 'c>>,
+
+    /// Notes displayed after the source extract
+    notes: Vec<ReportText<'c>>,
+
+    /// Help messages displayed after the notes
+    help: Vec<Cow<'c, str>>,
+
+    /// Suggested edits, displayed after the help messages
+    suggestions: Vec<ReportSuggestion<'c>>,
+
+    /// Rendering options
+    options: ReportOptions,
+}
+
+/// Options for rendering an [`ErrorReport`]
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Use ANSI escape codes to style the output
+    pub styled: bool,
+
+    /// Number of lines to display before and after the annotated ones
+    pub context_lines: usize,
+
+    /// Replace each tab character with the provided number of spaces
+    ///
+    /// If `None`, tabs are displayed as 4 spaces
+    pub tab_width: Option<usize>,
+
+    /// Title of the report, replacing the default one (e.g. "Parsing failed")
+    pub title: Option<Cow<'static, str>>,
+
+    /// Maximum width of the output, long lines being trimmed to fit in it
+    pub max_width: Option<usize>,
+
+    /// Build the URL of the page explaining an error code, linked from the title
+    pub code_url: Option<fn(&str) -> String>,
+
+    /// Formatter for the built-in messages (see [`MessageFormatter`])
+    pub formatter: &'static (dyn MessageFormatter + Send + Sync),
+}
+
+impl ReportOptions {
+    /// Default options
+    pub const fn new() -> Self {
+        Self {
+            styled: true,
+            context_lines: 2,
+            tab_width: None,
+            title: None,
+            max_width: None,
+            code_url: None,
+            formatter: &DefaultFormatter,
+        }
+    }
+
+    /// Default options, without any styling
+    pub const fn plain() -> Self {
+        let mut options = Self::new();
+        options.styled = false;
+        options
+    }
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Text displayed in an [`ErrorReport`]
+///
+/// Built-in messages are only formatted when the report is displayed,
+/// using the formatter of its [`ReportOptions`].
+#[derive(Clone)]
+enum ReportText<'c> {
+    /// Text provided as is
+    Text(Cow<'c, str>),
+
+    /// Message of a parsing error
+    Error(&'c ParsingError),
+
+    /// Message of a label or of a suggestion
+    Message(&'c Message),
+
+    /// Frame of a parsing error's context, with its line number if it is known
+    ContextFrame(&'c ContextFrame, Option<usize>),
+
+    /// Message of an error that caused a parsing error
+    CausedBy(String),
+}
+
+impl ReportText<'_> {
+    /// Format the text with the provided formatter
+    fn format(&self, formatter: &dyn MessageFormatter) -> Cow<'_, str> {
+        match self {
+            Self::Text(text) => Cow::Borrowed(text),
+            Self::Error(err) => err.message_with(formatter),
+            Self::Message(message) => message.format_with(formatter),
+            Self::ContextFrame(frame, line) => Cow::Owned(formatter.context_frame(frame, *line)),
+            Self::CausedBy(cause) => Cow::Owned(formatter.caused_by(cause)),
+        }
+    }
+}
+
+/// Additional location displayed in an [`ErrorReport`]
+#[derive(Clone)]
+struct ReportLabel<'c> {
+    /// Offset in the source content
+    offset: usize,
+
+    /// Number of input bytes covered by the label
+    len: usize,
+
+    /// Label's message
+    message: ReportText<'c>,
+}
+
+/// Additional location displayed in an [`ErrorReport`], in another source
+#[derive(Clone)]
+struct ExternalLabel<'a, 'b, 'c> {
+    /// Source content the label is located in
+    source: &'a str,
+
+    /// Path to the source
+    source_path: &'b str,
+
+    /// Index of the source's lines, if available
+    line_index: Option<&'a LineIndex>,
+
+    /// Label itself
+    label: ReportLabel<'c>,
+}
+
+/// Suggested edit displayed in an [`ErrorReport`]
+#[derive(Clone)]
+struct ReportSuggestion<'c> {
+    /// Offset in the source content
+    offset: usize,
+
+    /// Number of input bytes to replace
+    len: usize,
+
+    /// Content to replace them with
+    replacement: Cow<'c, str>,
+
+    /// Description of the edit
+    message: ReportText<'c>,
 }
 
 impl<'a, 'b, 'c> ErrorReport<'a, 'b, 'c> {
     /// Create an error report from a [`ParsingError`]
+    ///
+    /// Secondary labels and suggestions located in another file than the error are not displayed.
+    /// To display them, see [`ErrorReport::from_source_map`].
     pub fn parsing_error(
         source: &'a str,
         source_path: &'b str,
         parsing_err: &'c ParsingError,
     ) -> Self {
+        let file_id = parsing_err.inner().at().start.file_id;
+
+        // Only index the source's lines if there are context frames to locate
+        let line_index = LazyCell::new(|| LineIndex::new(source));
+
         Self {
             source,
             source_path,
-            err_msg: match parsing_err.critical_message() {
-                Some(nature) => Cow::Borrowed(nature),
-                None => match parsing_err.inner().expected() {
-                    ParserExpectation::Char(c) => Cow::Owned(format!("expected char '{c}'")),
-                    ParserExpectation::Str(str) => Cow::Owned(format!("expected '{str}'")),
-                    ParserExpectation::Custom(msg) => Cow::Borrowed(msg),
-                    ParserExpectation::Break => Cow::Borrowed("got break (should not be possible)"),
-                },
-            },
+            line_index: None,
+            err_msg: ReportText::Error(parsing_err),
             offset: parsing_err.inner().at().start.offset,
             len: parsing_err.inner().at().len,
+            severity: Severity::Error,
+            code: parsing_err.code().map(Cow::Borrowed),
+            secondary_labels: parsing_err
+                .secondary_labels()
+                .iter()
+                .filter(|label| label.at.start.file_id == file_id)
+                .map(|label| ReportLabel {
+                    offset: label.at.start.offset,
+                    len: label.at.len,
+                    message: ReportText::Message(&label.message),
+                })
+                .collect(),
+            external_labels: vec![],
+            notes: Self::parsing_error_notes(parsing_err, |frame| {
+                if frame.at.file_id == file_id {
+                    frame.at.compute_coords_with(&line_index).ok()
+                } else {
+                    None
+                }
+                .map(|coords| coords.line + 1)
+            }),
+            help: parsing_err
+                .help()
+                .iter()
+                .map(|help| Cow::Borrowed(help.as_ref()))
+                .collect(),
+            suggestions: parsing_err
+                .suggestions()
+                .iter()
+                .filter(|suggestion| suggestion.at.start.file_id == file_id)
+                .map(|suggestion| ReportSuggestion {
+                    offset: suggestion.at.start.offset,
+                    len: suggestion.at.len,
+                    replacement: Cow::Borrowed(&suggestion.replacement),
+                    message: ReportText::Message(&suggestion.message),
+                })
+                .collect(),
+            options: ReportOptions::new(),
+        }
+    }
+
+    /// Create an error report from a [`ParsingError`], using the files of a [`SourceMap`]
+    ///
+    /// Fails if the error is not located in a file of the source map.
+    /// Secondary labels located in other files of the source map are displayed as well.
+    pub fn from_source_map(
+        source_map: &'a SourceMap,
+        parsing_err: &'c ParsingError,
+    ) -> Result<ErrorReport<'a, 'a, 'c>, SourceMapErr> {
+        let file = source_map.file(parsing_err.inner().at().start.file_id)?;
+
+        let mut report = ErrorReport::parsing_error(file.content(), file.path(), parsing_err)
+            .with_line_index(file.line_index());
+
+        report.notes = Self::parsing_error_notes(parsing_err, |frame| {
+            source_map
+                .coords(frame.at)
+                .ok()
+                .map(|coords| coords.line + 1)
+        });
+
+        for label in parsing_err.secondary_labels() {
+            let label_file_id = label.at.start.file_id;
+
+            if label_file_id == parsing_err.inner().at().start.file_id {
+                continue;
+            }
+
+            // Labels in unknown files cannot be displayed
+            if let Ok(label_file) = source_map.file(label_file_id) {
+                report.external_labels.push(ExternalLabel {
+                    source: label_file.content(),
+                    source_path: label_file.path(),
+                    line_index: Some(label_file.line_index()),
+                    label: ReportLabel {
+                        offset: label.at.start.offset,
+                        len: label.at.len,
+                        message: ReportText::Message(&label.message),
+                    },
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Build the notes of a [`ParsingError`]'s report
+    ///
+    /// The provided function computes the line number of a context frame, if possible
+    fn parsing_error_notes(
+        parsing_err: &'c ParsingError,
+        line: impl Fn(&ContextFrame) -> Option<usize>,
+    ) -> Vec<ReportText<'c>> {
+        parsing_err
+            .context()
+            .iter()
+            .rev()
+            .map(|frame| ReportText::ContextFrame(frame, line(frame)))
+            .chain(
+                parsing_err
+                    .notes()
+                    .iter()
+                    .map(|note| ReportText::Text(Cow::Borrowed(note.as_ref()))),
+            )
+            .chain(
+                parsing_err
+                    .source_causes()
+                    .into_iter()
+                    .map(ReportText::CausedBy),
+            )
+            .collect()
+    }
+
+    /// Create a report from a [`Diagnostic`]
+    pub fn diagnostic(source: &'a str, source_path: &'b str, diagnostic: &'c Diagnostic) -> Self {
+        Self {
+            source,
+            source_path,
+            line_index: None,
+            err_msg: ReportText::Text(Cow::Borrowed(&diagnostic.message)),
+            offset: diagnostic.at.start.offset,
+            len: diagnostic.at.len,
+            severity: diagnostic.severity,
+            code: None,
+            secondary_labels: vec![],
+            external_labels: vec![],
+            notes: vec![],
+            help: vec![],
+            suggestions: vec![],
+            options: ReportOptions::new(),
         }
     }
 
@@ -60,11 +356,114 @@ impl<'a, 'b, 'c> ErrorReport<'a, 'b, 'c> {
         Self {
             source,
             source_path,
+            line_index: None,
             offset: at.start.offset,
             len: at.len,
-            err_msg: Cow::Borrowed(msg),
+            err_msg: ReportText::Text(Cow::Borrowed(msg)),
+            severity: Severity::Error,
+            code: None,
+            secondary_labels: Vec::new(),
+            external_labels: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+            suggestions: Vec::new(),
+            options: ReportOptions::new(),
         }
     }
+
+    /// Set the error code, displayed in the title
+    pub fn with_code(mut self, code: impl Into<Cow<'c, str>>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Point at an additional location related to the error
+    pub fn with_secondary_label(
+        mut self,
+        at: InputRange,
+        message: impl Into<Cow<'c, str>>,
+    ) -> Self {
+        self.secondary_labels.push(ReportLabel {
+            offset: at.start.offset,
+            len: at.len,
+            message: ReportText::Text(message.into()),
+        });
+
+        self
+    }
+
+    /// Point at an additional location related to the error, located in another source
+    pub fn with_secondary_label_in(
+        mut self,
+        source: &'a str,
+        source_path: &'b str,
+        at: InputRange,
+        message: impl Into<Cow<'c, str>>,
+    ) -> Self {
+        self.external_labels.push(ExternalLabel {
+            source,
+            source_path,
+            line_index: None,
+            label: ReportLabel {
+                offset: at.start.offset,
+                len: at.len,
+                message: ReportText::Text(message.into()),
+            },
+        });
+
+        self
+    }
+
+    /// Add a note after the source extract
+    pub fn with_note(mut self, note: impl Into<Cow<'c, str>>) -> Self {
+        self.notes.push(ReportText::Text(note.into()));
+        self
+    }
+
+    /// Add a help message after the source extract
+    pub fn with_help(mut self, help: impl Into<Cow<'c, str>>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Suggest replacing a range of the source, displayed after the help messages
+    pub fn with_suggestion(
+        mut self,
+        at: InputRange,
+        replacement: impl Into<Cow<'c, str>>,
+        message: impl Into<Cow<'c, str>>,
+    ) -> Self {
+        self.suggestions.push(ReportSuggestion {
+            offset: at.start.offset,
+            len: at.len,
+            replacement: replacement.into(),
+            message: ReportText::Text(message.into()),
+        });
+
+        self
+    }
+
+    /// Use a pre-built index of the source's lines, instead of building one when displaying the report
+    ///
+    /// The index must have been built from the report's source.
+    pub fn with_line_index(mut self, line_index: &'a LineIndex) -> Self {
+        self.line_index = Some(line_index);
+        self
+    }
+
+    /// Set the rendering options
+    pub fn with_options(mut self, options: ReportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Compute the range of the source to annotate
+    ///
+    /// Trailing line breaks are excluded, so a range ending a line is not
+    /// displayed as spanning over the next one.
+    fn annotated_range(&self, offset: usize, len: usize) -> Range<usize> {
+        annotated_range(self.source, offset, len)
+    }
 }
 
 impl std::fmt::Display for ErrorReport<'_, '_, '_> {
@@ -72,84 +471,319 @@ impl std::fmt::Display for ErrorReport<'_, '_, '_> {
         let ErrorReport {
             source,
             source_path,
+            line_index,
             err_msg,
             offset,
             len,
+            severity,
+            code,
+            secondary_labels,
+            external_labels,
+            notes,
+            help,
+            suggestions,
+            options,
         } = self;
 
-        let line = source[..*offset].chars().filter(|&c| c == '\n').count();
+        let formatter = options.formatter;
+        let err_msg = err_msg.format(formatter);
 
-        let extract_start_line = line.saturating_sub(2) + 1;
+        let primary_range = self.annotated_range(*offset, *len);
 
-        let extract_start_offset = if extract_start_line == 1 {
-            0
-        } else {
-            let mut line_counter = 1;
-            let mut shift = 0;
-
-            source[..*offset]
-                .chars()
-                .find_map(|c| {
-                    if c == '\n' {
-                        line_counter += 1;
-                    }
-
-                    shift += c.len_utf8();
-
-                    if line_counter == extract_start_line {
-                        Some(shift)
-                    } else {
-                        None
-                    }
-                })
-                .unwrap_or(0)
-        };
+        let secondary_ranges = secondary_labels
+            .iter()
+            .map(|label| self.annotated_range(label.offset, label.len))
+            .collect::<Vec<_>>();
+
+        // Lowest and highest offsets to display
+        let first = secondary_ranges
+            .iter()
+            .map(|range| range.start)
+            .fold(primary_range.start, usize::min);
+
+        let last = secondary_ranges
+            .iter()
+            .map(|range| range.end)
+            .fold(primary_range.end, usize::max);
 
-        let mut line_counter = 0;
+        let built_line_index;
 
-        let afterwards = &source[offset + len..].chars().position(|c| {
-            if c == '\n' {
-                line_counter += 1;
+        let line_index = match line_index {
+            Some(line_index) => line_index,
+            None => {
+                built_line_index = LineIndex::new(source);
+                &built_line_index
             }
+        };
 
-            line_counter == 2
-        });
+        // Both offsets are in bounds, as annotated ranges are clamped to the source
+        let first_line = line_index.line_of(first).unwrap();
+        let last_line = line_index.line_of(last).unwrap();
+
+        // Display context lines before the first annotation...
+        let extract_first_line = first_line.saturating_sub(options.context_lines);
+        let extract_start = line_index.line_range(extract_first_line).unwrap().start;
+        let extract_start_line = extract_first_line + 1;
+
+        // ...and after the last one, except for the empty line following a final line break
+        let mut max_line = line_index.line_count() - 1;
+
+        if source.ends_with('\n') && last_line < max_line {
+            max_line -= 1;
+        }
 
-        let extract_end = match afterwards {
-            Some(pos) => offset + len + pos + 1,
-            None => source.len(),
+        let extract_last_line = (last_line + options.context_lines).min(max_line);
+        let extract_end = line_index.line_range(extract_last_line).unwrap().end;
+
+        let raw_extract = &source[extract_start..extract_end];
+        let extract = expand_tabs(raw_extract, options.tab_width);
+
+        // Location of the error, computed from the original source as tabs may be expanded
+        let primary_line = line_index.line_of(primary_range.start).unwrap();
+        let primary_line_start = line_index.line_range(primary_line).unwrap().start;
+        let primary_col = source[primary_line_start..primary_range.start]
+            .chars()
+            .count()
+            + 1;
+
+        // Convert a range of the source into a range of the extract
+        let extract_range = |range: Range<usize>| {
+            let convert =
+                |pos: usize| expanded_offset(raw_extract, pos - extract_start, options.tab_width);
+
+            convert(range.start)..convert(range.end)
         };
 
-        // NOTE: we add a space at the end of the error's line
-        // as the reporting library doesn't support displaying
-        // offsets after a line's last character
-        let extract = format!("{} ", &source[extract_start_offset..extract_end]);
+        let level = match severity {
+            Severity::Error => Level::ERROR,
+            Severity::Warning => Level::WARNING,
+            Severity::Note => Level::NOTE,
+        };
 
-        // Same thing for the error range source
-        let range_chars_len = if offset + (*len).max(1) == source.len() + 1 {
-            1
-        } else {
-            source[*offset..*offset + (*len).max(1)].len()
+        let title = match &options.title {
+            Some(title) => Cow::Borrowed(title.as_ref()),
+            None => Cow::Owned(formatter.report_title(*severity)),
         };
 
-        let snippet = Level::Error.title("Parsing failed").snippet(
-            Snippet::source(&extract)
+        let snippet =
+            Snippet::source(extract.as_ref())
                 .line_start(extract_start_line)
-                .origin(source_path)
-                .fold(false)
+                .fold(!secondary_labels.is_empty())
                 .annotation(
-                    Level::Error
-                        .span(
-                            offset - extract_start_offset
-                                ..offset - extract_start_offset + range_chars_len,
-                        )
-                        .label(err_msg.as_ref()),
-                ),
-        );
-
-        let renderer = Renderer::styled();
-        let rendered = renderer.render(snippet);
-
-        write!(f, "{rendered}")
+                    AnnotationKind::Primary
+                        .span(extract_range(primary_range))
+                        .label(err_msg),
+                )
+                .annotations(secondary_labels.iter().zip(secondary_ranges).map(
+                    |(label, range)| {
+                        AnnotationKind::Context
+                            .span(extract_range(range))
+                            .label(label.message.format(formatter))
+                    },
+                ));
+
+        let mut title = level.clone().primary_title(title);
+
+        if let Some(code) = code {
+            title = title.id(code.as_ref());
+
+            if let Some(code_url) = options.code_url {
+                title = title.id_url(code_url(code));
+            }
+        }
+
+        // Group the labels located in other sources by source
+        let mut external_sources = Vec::<(&ExternalLabel, Vec<&ReportLabel>)>::new();
+
+        for external in external_labels {
+            match external_sources
+                .iter_mut()
+                .find(|(other, _)| other.source_path == external.source_path)
+            {
+                Some((_, labels)) => labels.push(&external.label),
+                None => external_sources.push((external, vec![&external.label])),
+            }
+        }
+
+        // Only display the lines covered by the labels of each source
+        let external_snippets = external_sources.into_iter().map(|(external, labels)| {
+            let ranges = labels
+                .iter()
+                .map(|label| annotated_range(external.source, label.offset, label.len))
+                .collect::<Vec<_>>();
+
+            let first = ranges.iter().map(|range| range.start).min().unwrap();
+            let last = ranges.iter().map(|range| range.end).max().unwrap();
+
+            let (line_start, lines) =
+                covering_lines(external.source, external.line_index, first..last);
+
+            let raw_lines = &external.source[lines.clone()];
+
+            let convert =
+                |pos: usize| expanded_offset(raw_lines, pos - lines.start, options.tab_width);
+
+            Snippet::source(expand_tabs(raw_lines, options.tab_width))
+                .line_start(line_start)
+                .path(external.source_path)
+                .fold(true)
+                .annotations(labels.iter().zip(ranges).map(|(label, range)| {
+                    AnnotationKind::Context
+                        .span(convert(range.start)..convert(range.end))
+                        .label(label.message.format(formatter))
+                }))
+        });
+
+        // The reporting library would compute the error's column from the displayed source,
+        // where tabs may be expanded, so the location is provided explicitly along with the title
+        // and the extract is displayed without a path, in the next group. As the library only
+        // draws separators around the first group, it is left empty and they are added as padding.
+        let mut body = Group::with_level(level.clone())
+            .element(snippet)
+            .elements(external_snippets);
+
+        if !notes.is_empty() || !help.is_empty() {
+            body = body.element(Padding);
+        }
+
+        body = body
+            .elements(
+                notes
+                    .iter()
+                    .map(|note| Level::NOTE.message(note.format(formatter))),
+            )
+            .elements(help.iter().map(|help| Level::HELP.message(help.as_ref())));
+
+        // A separator is drawn before the suggestions, unless a message precedes them
+        if !suggestions.is_empty() && notes.is_empty() && help.is_empty() {
+            body = body.element(Padding);
+        }
+
+        let report = [
+            Group::with_level(level),
+            title.element(
+                Origin::path(*source_path)
+                    .line(primary_line + 1)
+                    .char_column(primary_col),
+            ),
+            body,
+        ]
+        .into_iter()
+        // Suggestions may be outside of the extract, so only display the lines they cover
+        .chain(suggestions.iter().map(|suggestion| {
+            let start = suggestion.offset.min(source.len());
+            let end = (suggestion.offset + suggestion.len).min(source.len());
+
+            let (line_start, lines) = covering_lines(source, Some(line_index), start..end);
+            let raw_lines = &source[lines.clone()];
+
+            let convert =
+                |pos: usize| expanded_offset(raw_lines, pos - lines.start, options.tab_width);
+
+            Level::HELP
+                .secondary_title(suggestion.message.format(formatter))
+                .element(
+                    Snippet::source(expand_tabs(raw_lines, options.tab_width))
+                        .line_start(line_start)
+                        .path(*source_path)
+                        .patch(Patch::new(
+                            convert(start)..convert(end),
+                            suggestion.replacement.as_ref(),
+                        )),
+                )
+        }))
+        .collect::<Vec<_>>();
+
+        let renderer = if options.styled {
+            Renderer::styled()
+        } else {
+            Renderer::plain()
+        };
+
+        let renderer = match options.max_width {
+            Some(width) => renderer.term_width(width),
+            None => renderer,
+        };
+
+        let rendered = renderer.render(&report);
+
+        // Skip the line break following the empty group
+        write!(f, "{}", rendered.strip_prefix('\n').unwrap_or(&rendered))
+    }
+}
+
+/// Compute the range of a source to annotate
+///
+/// See [`ErrorReport::annotated_range`]
+fn annotated_range(source: &str, offset: usize, len: usize) -> Range<usize> {
+    let offset = offset.min(source.len());
+    let end = (offset + len).min(source.len());
+
+    let trimmed = source[offset..end].trim_end_matches(['\r', '\n']);
+
+    offset..offset + trimmed.len()
+}
+
+/// Compute the range of the lines covering a range of a source, and the number of the first one
+///
+/// Uses the source's line index if provided, and scans the source otherwise.
+/// Line breaks are excluded, unless the range covers them.
+fn covering_lines(
+    source: &str,
+    line_index: Option<&LineIndex>,
+    range: Range<usize>,
+) -> (usize, Range<usize>) {
+    let (first_line, start, end) = match line_index {
+        // Both offsets are in bounds, as ranges are clamped to the source
+        Some(line_index) => {
+            let first_line = line_index.line_of(range.start).unwrap();
+            let last_line = line_index.line_of(range.end).unwrap();
+
+            (
+                first_line,
+                line_index.line_range(first_line).unwrap().start,
+                line_index.line_range(last_line).unwrap().end,
+            )
+        }
+
+        None => {
+            let before = &source[..range.start];
+            let start = before.rfind('\n').map_or(0, |pos| pos + 1);
+
+            let end = source[range.end..]
+                .find('\n')
+                .map_or(source.len(), |pos| range.end + pos);
+
+            let end = match source[..end].strip_suffix('\r') {
+                Some(line) => line.len(),
+                None => end,
+            };
+
+            (before.matches('\n').count(), start, end)
+        }
+    };
+
+    (first_line + 1, start..end.max(range.end))
+}
+
+/// Replace each tab character with the provided number of spaces, if any
+fn expand_tabs(text: &str, tab_width: Option<usize>) -> Cow<'_, str> {
+    match tab_width {
+        Some(width) => Cow::Owned(text.replace('\t', &" ".repeat(width))),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Convert an offset in a text into an offset in the same text with its tabs expanded
+///
+/// See [`expand_tabs`]
+fn expanded_offset(text: &str, pos: usize, tab_width: Option<usize>) -> usize {
+    match tab_width {
+        Some(width) => {
+            let tabs = text[..pos.min(text.len())].matches('\t').count();
+            pos + tabs * width - tabs
+        }
+
+        None => pos,
     }
 }
@@ -0,0 +1,254 @@
+use std::cell::RefCell;
+
+use crate::{Diagnostic, ParsingError};
+
+/// State shared by all parsers during a parsing run
+///
+/// Unlike the [`ParserInput`](`crate::ParserInput`), it is not rolled back
+/// when a parser fails, which allows collecting information across backtracking.
+///
+/// The user state's type is part of the session's type, and of the [`InputKind`](`crate::InputKind`)
+/// of the inputs it is attached to (see [`Self::with_state`]).
+///
+/// See [`ParserInput::new`](`crate::ParserInput::new`)
+#[derive(Debug, Default)]
+pub struct ParsingSession<S = ()> {
+    /// Non-critical failure that happened the furthest in the input
+    furthest_failure: RefCell<Option<ParsingError>>,
+
+    /// Whether parsers are allowed to recover from errors
+    recovery: bool,
+
+    /// Errors parsers recovered from (see [`crate::ParserConstUtils::recover_with`])
+    recovered_errors: RefCell<Vec<ParsingError>>,
+
+    /// Non-fatal diagnostics emitted by parsers
+    diagnostics: RefCell<Vec<Diagnostic>>,
+
+    /// User-provided state (see [`Self::with_state`])
+    state: RefCell<S>,
+}
+
+/// State that can be threaded through parsing (see [`ParsingSession::with_state`])
+///
+/// As parsers may backtrack, the state must be able to restore itself to a previous snapshot.
+/// Snapshots are taken before each parser that may be backtracked from (see
+/// [`ParserInput::rollback_on_failure`](`crate::ParserInput::rollback_on_failure`)), so they should
+/// be cheap to create: for instance, the number of entries of an append-only table.
+pub trait UserState {
+    /// Snapshot of the state
+    type Snapshot;
+
+    /// Take a snapshot of the state
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Restore the state to a previous snapshot
+    fn restore(&mut self, snapshot: Self::Snapshot);
+}
+
+/// Absence of user state
+impl UserState for () {
+    type Snapshot = ();
+
+    fn snapshot(&self) {}
+
+    fn restore(&mut self, (): ()) {}
+}
+
+/// Snapshot of a [`ParsingSession`]'s rollbackable content
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SessionCheckpoint<S> {
+    recovered_errors: usize,
+    diagnostics: usize,
+    state: S,
+}
+
+impl ParsingSession {
+    /// Create a new, empty session
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: UserState> ParsingSession<S> {
+    /// Allow parsers to recover from critical errors
+    ///
+    /// See [`crate::ParserConstUtils::recover_with`]
+    pub fn with_recovery(mut self) -> Self {
+        self.recovery = true;
+        self
+    }
+
+    /// Attach a user state to the session, replacing its current one
+    ///
+    /// Parsers can read and update it (see [`crate::ParserInput::state`] and
+    /// [`crate::ParserInput::update_state`]), and updates are undone when the parser
+    /// that made them fails.
+    pub fn with_state<St: UserState>(self, state: St) -> ParsingSession<St> {
+        let Self {
+            furthest_failure,
+            recovery,
+            recovered_errors,
+            diagnostics,
+            state: _,
+        } = self;
+
+        ParsingSession {
+            furthest_failure,
+            recovery,
+            recovered_errors,
+            diagnostics,
+            state: RefCell::new(state),
+        }
+    }
+
+    /// Take the user state back from the session
+    pub fn into_state(self) -> S {
+        self.state.into_inner()
+    }
+
+    /// Read the user state
+    pub fn state<R>(&self, func: impl FnOnce(&S) -> R) -> R {
+        func(&self.state.borrow())
+    }
+
+    /// Update the user state
+    ///
+    /// The update will be undone if the parser that made it fails.
+    pub fn update_state<R>(&self, func: impl FnOnce(&mut S) -> R) -> R {
+        func(&mut self.state.borrow_mut())
+    }
+
+    /// Check if parsers are allowed to recover from critical errors
+    pub fn recovery_enabled(&self) -> bool {
+        self.recovery
+    }
+
+    /// Record a failure
+    ///
+    /// It will only be kept if it happened at least as far as the furthest recorded failure.
+    /// Failures happening at the same location have their expectations merged.
+    ///
+    /// Critical failures are not recorded, as they are propagated as they are.
+    pub fn record_failure(&self, err: &ParsingError) {
+        if err.is_critical() {
+            return;
+        }
+
+        let mut furthest = self.furthest_failure.borrow_mut();
+
+        let merged = match furthest.take() {
+            None => err.clone(),
+
+            Some(prev) if prev.inner().at().start.offset != err.inner().at().start.offset => {
+                if prev.inner().at().start.offset > err.inner().at().start.offset {
+                    *furthest = Some(prev);
+                    return;
+                }
+
+                err.clone()
+            }
+
+            Some(prev)
+                if prev.inner().at().len >= err.inner().at().len
+                    && prev.inner().expected().includes(err.inner().expected())
+                    && prev.context().len() >= err.context().len()
+                    && (prev.has_details() || !err.has_details()) =>
+            {
+                prev
+            }
+
+            Some(prev) => prev.merge_alternative(err.clone()),
+        };
+
+        *furthest = Some(merged);
+    }
+
+    /// Get the furthest recorded failure
+    pub fn furthest_failure(&self) -> Option<ParsingError> {
+        self.furthest_failure.borrow().clone()
+    }
+
+    /// Run a function while discarding all failures recorded during its execution
+    pub fn without_failure_tracking<T>(&self, func: impl FnOnce() -> T) -> T {
+        let saved = self.furthest_failure.take();
+        let result = func();
+        self.furthest_failure.replace(saved);
+        result
+    }
+
+    /// Record an error a parser recovered from
+    pub fn record_recovered_error(&self, err: ParsingError) {
+        self.recovered_errors.borrow_mut().push(err);
+    }
+
+    /// Take all the errors parsers recovered from so far
+    pub fn take_recovered_errors(&self) -> Vec<ParsingError> {
+        self.recovered_errors.take()
+    }
+
+    /// Record a diagnostic
+    pub fn push_diagnostic(&self, diagnostic: Diagnostic) {
+        self.diagnostics.borrow_mut().push(diagnostic);
+    }
+
+    /// Take all the diagnostics emitted so far
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.take()
+    }
+
+    /// Create a checkpoint to rollback to if a parser fails
+    pub(crate) fn checkpoint(&self) -> SessionCheckpoint<S::Snapshot> {
+        SessionCheckpoint {
+            recovered_errors: self.recovered_errors.borrow().len(),
+            diagnostics: self.diagnostics.borrow().len(),
+            state: self.state.borrow().snapshot(),
+        }
+    }
+
+    /// Discard everything that was recorded after the provided checkpoint,
+    /// except for failures, and restore the user state to its snapshot
+    pub(crate) fn rollback(&self, checkpoint: SessionCheckpoint<S::Snapshot>) {
+        let SessionCheckpoint {
+            recovered_errors,
+            diagnostics,
+            state,
+        } = checkpoint;
+
+        self.recovered_errors
+            .borrow_mut()
+            .truncate(recovered_errors);
+        self.diagnostics.borrow_mut().truncate(diagnostics);
+        self.state.borrow_mut().restore(state);
+    }
+
+    /// Get the most relevant error for a parsing that failed with the provided error
+    ///
+    /// Critical errors are returned as is. Otherwise, the furthest recorded failure is
+    /// returned if it happened further than the provided error, or merged with it
+    /// if it happened at the same location.
+    pub fn deepest_error(&self, err: ParsingError) -> ParsingError {
+        if err.is_critical() {
+            return err;
+        }
+
+        match self.furthest_failure.take() {
+            Some(furthest)
+                if furthest.inner().at().start.offset > err.inner().at().start.offset =>
+            {
+                furthest
+            }
+
+            // The error may have gained details (context, suggestions, ...) since it was recorded,
+            // so it is only merged if other parsers failed at the same location
+            Some(furthest)
+                if furthest.inner().at().start.offset == err.inner().at().start.offset
+                    && !err.inner().expected().includes(furthest.inner().expected()) =>
+            {
+                furthest.merge_alternative(err)
+            }
+
+            _ => err,
+        }
+    }
+}
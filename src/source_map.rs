@@ -0,0 +1,152 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    CoordsInString, FileId, InputLocation, InputRange, LineIndex, Parser, ParserNonConstUtils,
+    ParserResult, SourceFileID,
+};
+
+/// Registry of source files, resolving [`FileId`]s to their path and content
+///
+/// Each added file gets its own [`SourceFileID`], to use when parsing it,
+/// so locations from any of the files can be resolved afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Registered files, indexed by their ID
+    files: Vec<SourceFile>,
+}
+
+/// A file registered in a [`SourceMap`]
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// Path to the file
+    path: String,
+
+    /// Content of the file
+    content: String,
+
+    /// Index of the content's lines
+    line_index: LineIndex,
+}
+
+impl SourceFile {
+    /// Get the file's path
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the file's content
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Get the index of the file's lines
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+}
+
+impl SourceMap {
+    /// Create an empty source map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a file, returning its ID
+    pub fn add_file(
+        &mut self,
+        path: impl Into<String>,
+        content: impl Into<String>,
+    ) -> SourceFileID {
+        let content = content.into();
+
+        self.files.push(SourceFile {
+            path: path.into(),
+            line_index: LineIndex::new(&content),
+            content,
+        });
+
+        SourceFileID::from(u64::try_from(self.files.len() - 1).unwrap())
+    }
+
+    /// Read a file from the disk and register it, returning its ID
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> io::Result<SourceFileID> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+
+        Ok(self.add_file(path.to_string_lossy(), content))
+    }
+
+    /// Get a registered file
+    pub fn get(&self, id: SourceFileID) -> Option<&SourceFile> {
+        self.files.get(usize::try_from(u64::from(id)).ok()?)
+    }
+
+    /// Get the registered file a [`FileId`] refers to
+    pub fn file(&self, file_id: FileId) -> Result<&SourceFile, SourceMapErr> {
+        match file_id {
+            FileId::SourceFile(id) => self.get(id).ok_or(SourceMapErr::UnknownFile),
+            FileId::None | FileId::Internal | FileId::Custom(_) => Err(SourceMapErr::UnknownFile),
+        }
+    }
+
+    /// Iterate over all registered files
+    pub fn files(&self) -> impl Iterator<Item = (SourceFileID, &SourceFile)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (SourceFileID::from(u64::try_from(i).unwrap()), file))
+    }
+
+    /// Parse a registered file
+    ///
+    /// See [`ParserNonConstUtils::parse_str_with_file_id`]
+    ///
+    /// Fails if the file is not registered in the source map
+    pub fn parse<T>(
+        &self,
+        id: SourceFileID,
+        parser: &impl Parser<T>,
+    ) -> Result<ParserResult<T>, SourceMapErr> {
+        let file = self.get(id).ok_or(SourceMapErr::UnknownFile)?;
+
+        Ok(parser.parse_str_with_file_id(&file.content, FileId::SourceFile(id)))
+    }
+
+    /// Get the content covered by a range
+    pub fn extract(&self, at: InputRange) -> Result<&str, SourceMapErr> {
+        let content = &self.file(at.start.file_id)?.content;
+
+        content
+            .get(at.start.offset..at.start.offset + at.len)
+            .ok_or(SourceMapErr::OutOfBounds)
+    }
+
+    /// Compute the line and column number of a location, in the file it refers to
+    pub fn coords(&self, at: InputLocation) -> Result<CoordsInString, SourceMapErr> {
+        let file = self.file(at.file_id)?;
+
+        at.compute_coords_with(&file.line_index)
+            .map_err(|_| SourceMapErr::OutOfBounds)
+    }
+
+    /// Create a report for an error located in one of the files
+    ///
+    /// See [`crate::ErrorReport::from_source_map`]
+    #[cfg(feature = "error-reporting")]
+    pub fn report<'a, 'c>(
+        &'a self,
+        err: &'c crate::ParsingError,
+    ) -> Result<crate::ErrorReport<'a, 'a, 'c>, SourceMapErr> {
+        crate::ErrorReport::from_source_map(self, err)
+    }
+}
+
+/// Error returned when a location cannot be resolved by a [`SourceMap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceMapErr {
+    /// The location does not refer to a file registered in the source map
+    UnknownFile,
+
+    /// The location is out of bounds of its file
+    OutOfBounds,
+}
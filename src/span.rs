@@ -1,3 +1,5 @@
+use crate::{LineIndex, LspRange};
+
 /// Parse content from a parser
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -109,6 +111,9 @@ impl InputLocation {
     }
 
     /// Compute the line and column number of this location inside the provided input
+    ///
+    /// To compute the coordinates of many locations in the same input,
+    /// see [`InputLocation::compute_coords_with`].
     pub fn compute_coords_in(&self, input: &str) -> Result<CoordsInString, LocationOutOfBoundsErr> {
         if self.offset >= input.len() {
             return Err(LocationOutOfBoundsErr);
@@ -135,6 +140,16 @@ impl InputLocation {
         })
     }
 
+    /// Compute the line and column number of this location, using the index of the input's lines
+    ///
+    /// Unlike [`InputLocation::compute_coords_in`], the location may point at the end of the input.
+    pub fn compute_coords_with(
+        &self,
+        index: &LineIndex,
+    ) -> Result<CoordsInString, LocationOutOfBoundsErr> {
+        index.coords(self.offset)
+    }
+
     /// Extract the provided input's content covered by this location
     pub fn extract_str<'a>(&self, input: &'a str) -> Result<&'a str, LocationOutOfBoundsErr> {
         if self.offset < input.len() {
@@ -146,7 +161,8 @@ impl InputLocation {
 }
 
 /// Coordinates in a string
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CoordsInString {
     /// Line number (starting at 0)
     pub line: usize,
@@ -156,7 +172,7 @@ pub struct CoordsInString {
 }
 
 /// Indicate a [`InputRange`] is out-of-bounds of the provided input content
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LocationOutOfBoundsErr;
 
 impl std::fmt::Debug for InputLocation {
@@ -185,6 +201,17 @@ impl InputRange {
         Self { start, len }
     }
 
+    /// Convert this range to a range of the Language Server Protocol
+    ///
+    /// The provided index must have been built from the provided input.
+    pub fn to_lsp_range(
+        &self,
+        input: &str,
+        index: &LineIndex,
+    ) -> Result<LspRange, LocationOutOfBoundsErr> {
+        index.lsp_range(input, *self)
+    }
+
     /// Check if this range contains another one entirely
     pub const fn contains(&self, other: InputRange) -> Result<bool, InputRangeComparisonError> {
         match (self.start.file_id, other.start.file_id) {
@@ -0,0 +1,215 @@
+use std::{
+    io::{self, Read},
+    marker::PhantomData,
+};
+
+use crate::{
+    BinaryInput, ErrorKind, FileId, InputLocation, InputRange, Parser, ParserInput, ParserResult,
+    ParsingError, ParsingSession, Span,
+};
+
+/// Minimum number of bytes to read from the stream at once
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Iterator over the records parsed from a stream
+///
+/// The stream is read by chunks, which are dropped once the records they contain are parsed.
+/// Locations are absolute offsets in the stream.
+///
+/// Iteration stops after the first error, which is either a parsing error
+/// or an I/O error (see [`ParsingError::source_error`]).
+///
+/// See [`parse_stream`](`crate::ParserNonConstUtils::parse_stream`)
+pub struct ParseStream<'p, T, P: Parser<T, BinaryInput>, R: Read> {
+    /// Record parser
+    parser: &'p P,
+
+    /// Stream to read from
+    reader: R,
+
+    /// File ID to use in locations
+    file_id: FileId,
+
+    /// Buffered content of the stream
+    buffer: Vec<u8>,
+
+    /// Number of bytes of the buffer that have already been parsed
+    consumed: usize,
+
+    /// Offset of the buffer's first byte in the stream
+    offset: usize,
+
+    /// Whether the end of the stream has been reached
+    eof: bool,
+
+    /// Whether iteration has stopped
+    done: bool,
+
+    _t: PhantomData<T>,
+}
+
+impl<'p, T, P: Parser<T, BinaryInput>, R: Read> ParseStream<'p, T, P, R> {
+    /// Create an iterator over the records parsed from a stream
+    pub fn new(parser: &'p P, reader: R, file_id: FileId) -> Self {
+        Self {
+            parser,
+            reader,
+            file_id,
+            buffer: vec![],
+            consumed: 0,
+            offset: 0,
+            eof: false,
+            done: false,
+            _t: PhantomData,
+        }
+    }
+
+    /// Get the location of the first byte that hasn't been parsed yet
+    pub fn at(&self) -> InputLocation {
+        InputLocation {
+            file_id: self.file_id,
+            offset: self.offset + self.consumed,
+        }
+    }
+
+    /// Read more content from the stream, dropping the content that has already been parsed
+    ///
+    /// Reads at least as many bytes as are currently buffered, so parsing a record
+    /// spanning over many chunks remains linear.
+    fn fill(&mut self) -> io::Result<()> {
+        self.buffer.drain(..self.consumed);
+        self.offset += self.consumed;
+        self.consumed = 0;
+
+        let len = self.buffer.len();
+
+        self.buffer.resize(len + CHUNK_SIZE.max(len), 0);
+
+        let read = loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => break read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.buffer.truncate(len);
+                    return Err(err);
+                }
+            }
+        };
+
+        self.buffer.truncate(len + read);
+        self.eof = read == 0;
+
+        Ok(())
+    }
+
+    /// Parse the next record from the buffered content
+    ///
+    /// Returns [`None`] if more content is required to know the result
+    fn parse_buffered(&self) -> Option<ParserResult<T>> {
+        let buffered = &self.buffer[self.consumed..];
+
+        let session = ParsingSession::new();
+
+        let result = self
+            .parser
+            .parse(&mut ParserInput::new_bytes(
+                buffered,
+                self.file_id,
+                &session,
+            ))
+            .map_err(|err| session.deepest_error(err));
+
+        let end = |err: &ParsingError| err.inner().at().start.offset + err.inner().at().len;
+
+        // A record reaching the end of the buffered content may continue after it,
+        // and a failure there (even a discarded one) may be due to the content not being read yet
+        let reached = match &result {
+            Ok(span) => session
+                .furthest_failure()
+                .map_or(span.at.len, |err| end(&err).max(span.at.len)),
+            Err(err) => end(err),
+        };
+
+        if !self.eof && reached >= buffered.len() - incomplete_utf8_suffix(buffered) {
+            return None;
+        }
+
+        Some(result)
+    }
+}
+
+impl<T, P: Parser<T, BinaryInput>, R: Read> Iterator for ParseStream<'_, T, P, R> {
+    type Item = ParserResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            if self.consumed == self.buffer.len() && self.eof {
+                self.done = true;
+                return None;
+            }
+
+            let result = match self.consumed < self.buffer.len() {
+                true => self.parse_buffered(),
+                false => None,
+            };
+
+            let Some(result) = result else {
+                if let Err(err) = self.fill() {
+                    self.done = true;
+                    return Some(Err(ParsingError::from_source(self.at().range(0), err)));
+                }
+
+                continue;
+            };
+
+            let base = self.at();
+
+            let shift = |at: InputRange| InputRange::new(base.add(at.start.offset), at.len);
+
+            return Some(match result {
+                Ok(span) if span.at.len == 0 => {
+                    self.done = true;
+
+                    Err(ParsingError::from_kind(
+                        base.range(0),
+                        ErrorKind::EmptyRecord,
+                    ))
+                }
+
+                Ok(span) => {
+                    self.consumed += span.at.len;
+                    Ok(Span::ate(shift(span.at), span.data))
+                }
+
+                Err(err) => {
+                    self.done = true;
+                    Err(err.map_ranges(shift))
+                }
+            });
+        }
+    }
+}
+
+/// Get the length of the incomplete UTF-8 character some bytes end with, if any
+fn incomplete_utf8_suffix(bytes: &[u8]) -> usize {
+    let tail = &bytes[bytes.len().saturating_sub(3)..];
+
+    let Some(lead) = tail.iter().rposition(|byte| byte & 0xC0 != 0x80) else {
+        return 0;
+    };
+
+    let char_len = match tail[lead] {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    };
+
+    let available = tail.len() - lead;
+
+    if available < char_len { available } else { 0 }
+}
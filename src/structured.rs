@@ -0,0 +1,285 @@
+use std::ops::Range;
+
+use crate::{
+    CoordsInString, DefaultFormatter, Diagnostic, FileId, InputRange, LineIndex,
+    LocationOutOfBoundsErr, MessageFormatter, ParsingError, Severity, SourceMap, SourceMapErr,
+};
+
+/// A machine-readable description of a [`ParsingError`] or a [`Diagnostic`]
+///
+/// Unlike the error itself, it contains everything required to display it
+/// (message, line and column numbers, ...) without access to the source.
+///
+/// It is serializable if the `serde` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredDiagnostic {
+    /// Severity of the diagnostic
+    pub severity: Severity,
+
+    /// Main message
+    pub message: String,
+
+    /// Error code (see [`ParsingError::code`])
+    pub code: Option<String>,
+
+    /// File the diagnostic is located in
+    pub file_id: FileId,
+
+    /// Range of bytes covered by the diagnostic
+    pub range: Range<usize>,
+
+    /// Coordinates of the range's start
+    pub start: CoordsInString,
+
+    /// Coordinates of the range's end
+    pub end: CoordsInString,
+
+    /// Whether the error is critical (see [`ParsingError::is_critical`])
+    pub critical: bool,
+
+    /// Whether the error is atomic (see [`ParsingError::atomic_error`])
+    pub atomic: bool,
+
+    /// Additional locations related to the diagnostic
+    pub labels: Vec<StructuredLabel>,
+
+    /// Notes, including the error's context (see [`ParsingError::context`])
+    pub notes: Vec<String>,
+
+    /// Help messages
+    pub help: Vec<String>,
+
+    /// Machine-applicable edits fixing the diagnostic
+    pub suggestions: Vec<StructuredSuggestion>,
+}
+
+/// Additional location of a [`StructuredDiagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredLabel {
+    /// Label's message
+    pub message: String,
+
+    /// File the label is located in
+    pub file_id: FileId,
+
+    /// Range of bytes covered by the label
+    pub range: Range<usize>,
+
+    /// Coordinates of the range's start
+    pub start: CoordsInString,
+
+    /// Coordinates of the range's end
+    pub end: CoordsInString,
+}
+
+/// Suggested edit of a [`StructuredDiagnostic`]
+///
+/// See [`crate::Suggestion`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredSuggestion {
+    /// Description of the edit
+    pub message: String,
+
+    /// Content to replace the range with
+    pub replacement: String,
+
+    /// File the edit applies to
+    pub file_id: FileId,
+
+    /// Range of bytes to replace
+    pub range: Range<usize>,
+
+    /// Coordinates of the range's start
+    pub start: CoordsInString,
+
+    /// Coordinates of the range's end
+    pub end: CoordsInString,
+}
+
+impl StructuredDiagnostic {
+    /// Describe a [`ParsingError`] that happened while parsing the provided input
+    ///
+    /// Secondary labels and suggestions located in another file than the error are left out.
+    /// To keep them, see [`StructuredDiagnostic::from_source_map`].
+    pub fn from_parsing_error(
+        input: &str,
+        err: &ParsingError,
+    ) -> Result<Self, LocationOutOfBoundsErr> {
+        Self::from_parsing_error_with(&LineIndex::new(input), err)
+    }
+
+    /// Describe a [`ParsingError`], using the index of the lines of the input it happened in
+    ///
+    /// See [`StructuredDiagnostic::from_parsing_error`]
+    pub fn from_parsing_error_with(
+        index: &LineIndex,
+        err: &ParsingError,
+    ) -> Result<Self, LocationOutOfBoundsErr> {
+        let file_id = err.inner().at().start.file_id;
+        let coords = range_coords(index, err.inner().at())?;
+
+        Self::describe_parsing_error(err, coords, |at| {
+            if at.start.file_id == file_id {
+                range_coords(index, at).map(Some)
+            } else {
+                Ok(None)
+            }
+        })
+    }
+
+    /// Describe a [`ParsingError`] located in one of the files of a [`SourceMap`]
+    ///
+    /// Fails if the error is not located in a file of the source map.
+    /// Secondary labels and suggestions are located in their own file,
+    /// the ones located in a file unknown to the source map being left out.
+    pub fn from_source_map(
+        source_map: &SourceMap,
+        err: &ParsingError,
+    ) -> Result<Self, SourceMapErr> {
+        let coords = source_map_coords(source_map, err.inner().at())?;
+
+        Self::describe_parsing_error(err, coords, |at| match source_map_coords(source_map, at) {
+            Ok(coords) => Ok(Some(coords)),
+            Err(SourceMapErr::UnknownFile) => Ok(None),
+            Err(err) => Err(err),
+        })
+    }
+
+    /// Describe a [`ParsingError`], provided the coordinates of its location
+    ///
+    /// The provided function computes the coordinates of the other locations,
+    /// returning `None` for the ones that cannot be resolved.
+    fn describe_parsing_error<E>(
+        err: &ParsingError,
+        (start, end): (CoordsInString, CoordsInString),
+        coords: impl Fn(InputRange) -> Result<Option<(CoordsInString, CoordsInString)>, E>,
+    ) -> Result<Self, E> {
+        let mut labels = vec![];
+
+        for label in err.secondary_labels() {
+            if let Some((start, end)) = coords(label.at)? {
+                labels.push(StructuredLabel {
+                    message: label.message.to_string(),
+                    file_id: label.at.start.file_id,
+                    range: byte_range(label.at),
+                    start,
+                    end,
+                });
+            }
+        }
+
+        let mut suggestions = vec![];
+
+        for suggestion in err.suggestions() {
+            if let Some((start, end)) = coords(suggestion.at)? {
+                suggestions.push(StructuredSuggestion {
+                    message: suggestion.message.to_string(),
+                    replacement: suggestion.replacement.to_string(),
+                    file_id: suggestion.at.start.file_id,
+                    range: byte_range(suggestion.at),
+                    start,
+                    end,
+                });
+            }
+        }
+
+        let at = err.inner().at();
+
+        Ok(Self {
+            severity: Severity::Error,
+            message: err.message().into_owned(),
+            code: err.code().map(str::to_owned),
+            file_id: at.start.file_id,
+            range: byte_range(at),
+            start,
+            end,
+            critical: err.is_critical(),
+            atomic: err.atomic_error().is_some(),
+            labels,
+            notes: err
+                .context()
+                .iter()
+                .rev()
+                .map(|frame| {
+                    let line = coords(frame.at.range(0))
+                        .ok()
+                        .flatten()
+                        .map(|(start, _)| start.line + 1);
+
+                    DefaultFormatter.context_frame(frame, line)
+                })
+                .chain(err.notes().iter().map(|note| note.to_string()))
+                .chain(
+                    err.source_causes()
+                        .iter()
+                        .map(|cause| DefaultFormatter.caused_by(cause)),
+                )
+                .collect(),
+            help: err.help().iter().map(|help| help.to_string()).collect(),
+            suggestions,
+        })
+    }
+
+    /// Describe a [`Diagnostic`] emitted while parsing the provided input
+    pub fn from_diagnostic(
+        input: &str,
+        diagnostic: &Diagnostic,
+    ) -> Result<Self, LocationOutOfBoundsErr> {
+        Self::from_diagnostic_with(&LineIndex::new(input), diagnostic)
+    }
+
+    /// Describe a [`Diagnostic`], using the index of the lines of the input it was emitted in
+    ///
+    /// See [`StructuredDiagnostic::from_diagnostic`]
+    pub fn from_diagnostic_with(
+        index: &LineIndex,
+        diagnostic: &Diagnostic,
+    ) -> Result<Self, LocationOutOfBoundsErr> {
+        let (start, end) = range_coords(index, diagnostic.at)?;
+
+        Ok(Self {
+            severity: diagnostic.severity,
+            message: diagnostic.message.to_string(),
+            code: None,
+            file_id: diagnostic.at.start.file_id,
+            range: byte_range(diagnostic.at),
+            start,
+            end,
+            critical: false,
+            atomic: false,
+            labels: vec![],
+            notes: vec![],
+            help: vec![],
+            suggestions: vec![],
+        })
+    }
+}
+
+/// Get the range of bytes covered by an [`InputRange`]
+fn byte_range(at: InputRange) -> Range<usize> {
+    at.start.offset..at.start.offset + at.len
+}
+
+/// Compute the coordinates of an [`InputRange`]'s start and end
+fn range_coords(
+    index: &LineIndex,
+    at: InputRange,
+) -> Result<(CoordsInString, CoordsInString), LocationOutOfBoundsErr> {
+    Ok((
+        at.start.compute_coords_with(index)?,
+        at.start.add(at.len).compute_coords_with(index)?,
+    ))
+}
+
+/// Compute the coordinates of an [`InputRange`]'s start and end, in the file it refers to
+fn source_map_coords(
+    source_map: &SourceMap,
+    at: InputRange,
+) -> Result<(CoordsInString, CoordsInString), SourceMapErr> {
+    let file = source_map.file(at.start.file_id)?;
+
+    range_coords(file.line_index(), at).map_err(|_| SourceMapErr::OutOfBounds)
+}
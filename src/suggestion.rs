@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+
+use crate::{InputLocation, InputRange, Message};
+
+/// Machine-applicable edit fixing an error
+///
+/// Replaces a range of the input with the provided content.
+///
+/// See [`crate::ParsingError::with_suggestion`] and [`apply_suggestions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Range of the input to replace
+    pub at: InputRange,
+
+    /// Content to replace the range with
+    pub replacement: Cow<'static, str>,
+
+    /// Description of the edit (e.g. "insert ';' here")
+    pub message: Message,
+}
+
+impl Suggestion {
+    /// Suggest replacing a range of the input with the provided content
+    pub fn replace(
+        at: InputRange,
+        replacement: impl Into<Cow<'static, str>>,
+        message: impl Into<Message>,
+    ) -> Self {
+        Self {
+            at,
+            replacement: replacement.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Suggest inserting content at a location of the input
+    pub fn insert(
+        at: InputLocation,
+        content: impl Into<Cow<'static, str>>,
+        message: impl Into<Message>,
+    ) -> Self {
+        Self::replace(at.range(0), content, message)
+    }
+
+    /// Suggest removing a range of the input
+    pub fn remove(at: InputRange, message: impl Into<Message>) -> Self {
+        Self::replace(at, "", message)
+    }
+}
+
+/// Apply suggestions to the input they were made for
+///
+/// Suggestions are applied all at once, so their ranges refer to the original input.
+///
+/// Fails if two suggestions overlap (insertions at the same location excepted,
+/// in which case they are applied in the provided order) or if one of them is out of bounds.
+pub fn apply_suggestions<'a>(
+    input: &str,
+    suggestions: impl IntoIterator<Item = &'a Suggestion>,
+) -> Result<String, ApplySuggestionsErr> {
+    let mut suggestions = suggestions.into_iter().collect::<Vec<_>>();
+
+    // Stable sort to keep insertions at the same location in order,
+    // while applying them before a replacement starting at the same location
+    suggestions.sort_by_key(|suggestion| (suggestion.at.start.offset, suggestion.at.len));
+
+    let mut output = String::with_capacity(input.len());
+    let mut copied = 0;
+
+    for suggestion in suggestions {
+        let start = suggestion.at.start.offset;
+        let end = start + suggestion.at.len;
+
+        if start < copied {
+            return Err(ApplySuggestionsErr::Overlapping);
+        }
+
+        if end > input.len() || !input.is_char_boundary(start) || !input.is_char_boundary(end) {
+            return Err(ApplySuggestionsErr::OutOfBounds);
+        }
+
+        output.push_str(&input[copied..start]);
+        output.push_str(&suggestion.replacement);
+
+        copied = end;
+    }
+
+    output.push_str(&input[copied..]);
+
+    Ok(output)
+}
+
+/// Error returned by [`apply_suggestions`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplySuggestionsErr {
+    /// Two suggestions edit the same part of the input
+    Overlapping,
+
+    /// A suggestion is out of bounds of the input, or splits a character
+    OutOfBounds,
+}
@@ -0,0 +1,199 @@
+use parsy::{
+    ErrorKind, Found, ParserConstUtils, ParserExpectation, ParserNonConstUtils,
+    parsers::helpers::{
+        byte, filter_byte, int_be, int_le, just, just_bytes, length_prefixed, rest_bytes, sleb128,
+        take_bytes, uleb128, whitespaces,
+    },
+};
+
+#[test]
+fn packet_header() {
+    let parser = just_bytes(b"PK")
+        .ignore_then(byte(0x01))
+        .ignore_then(int_be::<u16>())
+        .then(int_le::<i32>())
+        .then(filter_byte(|b| b < 0x80))
+        .then_ignore(rest_bytes())
+        .full();
+
+    let parsed = parser
+        .parse_bytes(&[
+            b'P', b'K', 0x01, 0x12, 0x34, 0xFE, 0xFF, 0xFF, 0xFF, 0x7F, 0xAA,
+        ])
+        .unwrap();
+
+    assert_eq!(parsed.data, ((0x1234, -2), 0x7F));
+    assert_eq!(parsed.at.len, 11);
+
+    let err = parser.parse_bytes(&[b'P', b'K', 0x02]).unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 2);
+    assert_eq!(err.inner().found(), Some(&Found::Byte(0x02)));
+    assert_eq!(err.message(), "expected byte 0x01, found byte 0x02");
+
+    let err = parser.parse_bytes(&[b'P', b'K', 0x01, 0x12]).unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 3);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::Int { size: 2 })
+    );
+}
+
+#[test]
+fn leb128() {
+    let unsigned = uleb128().full();
+
+    assert_eq!(unsigned.parse_bytes(&[0x00]).unwrap().data, 0);
+    assert_eq!(
+        unsigned.parse_bytes(&[0xE5, 0x8E, 0x26]).unwrap().data,
+        624_485
+    );
+    assert_eq!(
+        unsigned
+            .parse_bytes(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01])
+            .unwrap()
+            .data,
+        u64::MAX
+    );
+
+    let err = unsigned
+        .parse_bytes(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02])
+        .unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::Leb128Overflow)
+    );
+
+    let err = unsigned.parse_bytes(&[0x80, 0x80]).unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::Leb128)
+    );
+    assert_eq!(err.inner().found(), Some(&Found::EndOfInput));
+
+    let signed = sleb128().full();
+
+    assert_eq!(signed.parse_bytes(&[0x02]).unwrap().data, 2);
+    assert_eq!(signed.parse_bytes(&[0x7E]).unwrap().data, -2);
+    assert_eq!(
+        signed.parse_bytes(&[0xC0, 0xBB, 0x78]).unwrap().data,
+        -123_456
+    );
+    assert_eq!(
+        signed
+            .parse_bytes(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F])
+            .unwrap()
+            .data,
+        i64::MIN
+    );
+}
+
+#[test]
+fn length_prefixed_fields() {
+    let parser = length_prefixed(int_be::<u8>(), just("name"))
+        .then(length_prefixed(uleb128(), take_bytes(2)))
+        .full();
+
+    let parsed = parser
+        .parse_bytes(&[4, b'n', b'a', b'm', b'e', 2, 0xCA, 0xFE])
+        .unwrap();
+
+    assert_eq!(parsed.data, ("name", vec![0xCA, 0xFE]));
+    assert_eq!(parsed.at.len, 8);
+
+    let err = parser
+        .parse_bytes(&[5, b'n', b'a', b'm', b'e', b's', 0])
+        .unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 5);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::UnconsumedField { remaining: 1 })
+    );
+
+    let err = parser
+        .parse_bytes(&[4, b'n', b'a', b'm', b'e', 3, 0xCA, 0xFE])
+        .unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 6);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::TruncatedField {
+            len: 3,
+            remaining: 2
+        })
+    );
+}
+
+#[test]
+fn text_in_binary_input() {
+    let parser = just("GIF89a").ignore_then(int_le::<u16>());
+
+    assert_eq!(parser.parse_bytes(b"GIF89a\x40\x01").unwrap().data, 320);
+
+    let err = parser.parse_bytes(b"GIF\xFF9a").unwrap_err();
+
+    assert_eq!(err.inner().found(), Some(&Found::Token("GIF".into())));
+
+    let parser = whitespaces().ignore_then(byte(0xFF));
+
+    assert_eq!(parser.parse_bytes(b" \t\xFF\xFE").unwrap().data, 0xFF);
+}
+
+#[test]
+fn invalid_utf8_string() {
+    let parser = take_bytes(2).collect_string();
+
+    assert_eq!(parser.parse_bytes(b"ab").unwrap().data, "ab");
+
+    let err = parser.parse_bytes(&[0xFF, 0xFE]).unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::InvalidUtf8)
+    );
+    assert_eq!(err.inner().at().len, 2);
+
+    let err = take_bytes(2)
+        .map_consumed_str(str::len)
+        .parse_bytes(&[b'a', 0xC3])
+        .unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::InvalidUtf8)
+    );
+}
+
+#[test]
+fn binary_parsers_in_text_input() {
+    // 'é' is encoded as 0xC3 0xA9, which can't be split
+    let err = just_bytes(b"a\xC3").parse_str("aé").unwrap_err();
+
+    assert_eq!(err.inner().found(), Some(&Found::Byte(0xA9)));
+    assert_eq!(err.inner().at().start.offset, 0);
+    assert_eq!(err.inner().at().len, 2);
+
+    let err = int_le::<u16>().parse_str("aé").unwrap_err();
+
+    assert_eq!(err.inner().found(), Some(&Found::Byte(0xA9)));
+
+    let err = int_le::<u32>().parse_str("aé").unwrap_err();
+
+    assert_eq!(err.inner().found(), Some(&Found::EndOfInput));
+
+    let err = take_bytes(2).parse_str("aé").unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::InvalidUtf8)
+    );
+    assert_eq!(err.inner().found(), Some(&Found::Byte(0xA9)));
+
+    let err = byte(0xC3).parse_str("é").unwrap_err();
+
+    assert_eq!(err.inner().found(), Some(&Found::Byte(0xA9)));
+}
@@ -1,7 +1,7 @@
-use std::{any::Any, ops::Deref, sync::LazyLock};
+use std::sync::LazyLock;
 
 use parsy::{
-    FileId, Parser, ParserConstUtils, ParserInput, ParserNonConstUtils,
+    FileId, Parser, ParserConstUtils, ParserInput, ParserNonConstUtils, ParsingSession, Span,
     parsers::{DebugType, helpers::*},
 };
 
@@ -83,28 +83,103 @@ pub fn utf8_boundaries() {
 
 #[test]
 pub fn context() {
-    let parser = get_context::<String>();
+    let parser = get_context::<&String>();
 
-    fn prepare_input(ctx: fn() -> Box<dyn Any>) -> ParserInput<'static> {
-        ParserInput::new_with_ctx("", FileId::None, ctx)
-    }
+    let ctx = "yoh".to_owned();
+    let session = ParsingSession::new();
 
     assert_eq!(
         parser
-            .parse(&mut prepare_input(|| Box::new("yoh".to_owned())))
+            .parse(&mut ParserInput::new_with_ctx(
+                "",
+                FileId::None,
+                &session,
+                &ctx
+            ))
             .unwrap()
-            .data
-            .deref(),
+            .data,
         "yoh"
     );
 
-    assert!(
+    assert_eq!(parser.parse_str_with_context("", &ctx).unwrap().data, "yoh");
+
+    let parser = custom(|input| {
+        let ctx: &Vec<String> = input.context();
+
+        Ok(Span::ate(input.range(0), ctx.len()))
+    });
+
+    assert_eq!(
         parser
-            .parse(&mut prepare_input(|| Box::new("yoh")))
-            .is_err()
+            .parse_str_with_context("", &vec!["a".to_owned(), "b".to_owned()])
+            .unwrap()
+            .data,
+        2
     );
 }
 
+#[test]
+pub fn scoped_context() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Mode {
+        newlines: bool,
+        depth: usize,
+    }
+
+    // Newlines are only allowed inside parentheses
+    let spaces = get_context::<Mode>()
+        .then(filter(|c| c == ' ' || c == '\n').repeated_into_vec())
+        .validate(|(mode, spaces)| mode.newlines || !spaces.contains(&'\n'));
+
+    let word = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .then(get_context::<Mode>())
+        .map(|(word, mode)| (word, mode.depth));
+
+    let words = recursive(|words| {
+        let group = words
+            .surrounded_by(char('('), char(')'))
+            .map_context(|mode: Mode| Mode {
+                newlines: true,
+                depth: mode.depth + 1,
+            });
+
+        word.map(|word| vec![word])
+            .or(group)
+            .padded_by(spaces)
+            .repeated_into_vec()
+            .map(|groups| groups.into_iter().flatten().collect::<Vec<_>>())
+    })
+    .full();
+
+    let top_level = Mode {
+        newlines: false,
+        depth: 0,
+    };
+
+    assert_eq!(
+        words
+            .parse_str_with_context("a (b\n(c)\nd) e", top_level)
+            .unwrap()
+            .data,
+        [
+            ("a".to_owned(), 0),
+            ("b".to_owned(), 1),
+            ("c".to_owned(), 2),
+            ("d".to_owned(), 1),
+            ("e".to_owned(), 0)
+        ]
+    );
+
+    assert!(words.parse_str_with_context("a (b)\ne", top_level).is_err());
+
+    let parser = get_context::<Mode>().with_context(top_level);
+
+    assert_eq!(parser.parse_str("").unwrap().data, top_level);
+}
+
 #[allow(dead_code)]
 fn simple_debug<T>(debug: DebugType<'_, '_, T>) {
     match debug {
@@ -0,0 +1,681 @@
+use std::{
+    error::Error,
+    fmt,
+    num::{IntErrorKind, ParseIntError},
+};
+
+use parsy::{
+    ApplySuggestionsErr, CoordsInString, Diagnostic, ErrorKind, FileId, Found, InputLocation,
+    ParserConstUtils, ParserExpectation, ParserNonConstUtils, ParserResult, ParsingError, Severity,
+    Span, StructuredDiagnostic, Suggestion, apply_suggestions,
+    parsers::helpers::{char, choice, custom, filter, just, lookahead, newline, silent_choice},
+};
+
+#[test]
+fn choice_merges_expectations() {
+    let parser = just("Hello ").then(choice((just("World"), just("world"))));
+
+    let err = parser.parse_str("Hello there").unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 6);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::OneOf(vec![
+            ParserExpectation::Str("World"),
+            ParserExpectation::Str("world")
+        ])
+    );
+    assert_eq!(
+        err.inner().expected().to_string(),
+        "expected one of 'World', 'world'"
+    );
+
+    let err = silent_choice((char('a'), just("bc"), char('a')))
+        .parse_str("d")
+        .unwrap_err();
+
+    assert_eq!(
+        err.inner().expected().to_string(),
+        "expected one of 'a', 'bc'"
+    );
+
+    let err = choice((just("ab").then(char('c')), just("a").then(char('d'))))
+        .parse_str("abe")
+        .unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 2);
+    assert_eq!(err.inner().expected(), &ParserExpectation::Char('c'));
+}
+
+#[test]
+fn furthest_failure() {
+    let parser = just("a").then(char('b')).or_not().then(char('c')).full();
+
+    let err = parser.parse_str("ad").unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 1);
+    assert_eq!(err.inner().expected(), &ParserExpectation::Char('b'));
+
+    let err = parser.parse_str("d").unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 0);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::OneOf(vec![
+            ParserExpectation::Str("a"),
+            ParserExpectation::Char('c')
+        ])
+    );
+
+    let atomic = just("a").then(char('b')).atomic_err("expected ab");
+
+    let err = atomic.or_not().then(char('c')).parse_str("ad").unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 1);
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Custom("expected ab".into())
+    );
+
+    // Discarded failures keep the context of the labelled parsers they went through
+    let err = just("a")
+        .then(char('b'))
+        .labelled("pair")
+        .or_not()
+        .then(char('c'))
+        .parse_str("ad")
+        .unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 1);
+    assert_eq!(err.context().len(), 1);
+}
+
+#[test]
+fn diagnostics() {
+    let parser = char('(')
+        .repeated()
+        .exactly(2)
+        .then(char(')').repeated().exactly(2))
+        .warn_if(|_| true, "redundant parentheses")
+        .or_not()
+        .then(char('a'))
+        .full();
+
+    let (result, diagnostics) = parser.parse_str_with_diagnostics("(())a");
+
+    assert!(result.is_ok());
+    assert_eq!(
+        diagnostics,
+        [Diagnostic::warning(
+            InputLocation {
+                file_id: FileId::None,
+                offset: 0
+            }
+            .range(4),
+            "redundant parentheses"
+        )]
+    );
+
+    let parser = char('(')
+        .warn_if(|_| true, "discarded")
+        .then(char('b'))
+        .or_not()
+        .then(char('('))
+        .full();
+
+    let (result, diagnostics) = parser.parse_str_with_diagnostics("(");
+
+    assert!(result.is_ok());
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn lookahead_discards_diagnostics() {
+    let parser = lookahead(char('(').warn_if(|_| true, "looked ahead"))
+        .then(char('(').warn_if(|_| true, "consumed"))
+        .full();
+
+    let (result, diagnostics) = parser.parse_str_with_diagnostics("(");
+
+    assert!(result.is_ok());
+    assert_eq!(
+        diagnostics,
+        [Diagnostic::warning(
+            InputLocation {
+                file_id: FileId::None,
+                offset: 0
+            }
+            .range(1),
+            "consumed"
+        )]
+    );
+
+    let parser = lookahead(
+        char('a')
+            .critical("expected 'a'")
+            .recover_with_fallback('?'),
+    )
+    .then(char('b'))
+    .full();
+
+    let (result, errors) = parser.parse_str_recovering("b");
+
+    assert_eq!(result, Some(('?', 'b')));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn owned_messages() {
+    let expected = 3;
+
+    let err = char('a')
+        .critical(format!("expected {expected} arguments"))
+        .parse_str("b")
+        .unwrap_err();
+
+    assert_eq!(
+        err.critical_message().as_deref(),
+        Some("expected 3 arguments")
+    );
+
+    let err = char('a')
+        .atomic_err(format!("unknown field `{}`", "colour"))
+        .parse_str("b")
+        .unwrap_err();
+
+    assert_eq!(err.atomic_error(), Some("unknown field `colour`"));
+
+    struct ArgsCount {
+        expected: usize,
+        found: usize,
+    }
+
+    impl fmt::Display for ArgsCount {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "expected {} arguments, found {}",
+                self.expected, self.found
+            )
+        }
+    }
+
+    let parser = custom(|input| -> ParserResult<()> {
+        Err(ParsingError::dynamic(
+            input.range(0),
+            ArgsCount { expected, found: 5 },
+        ))
+    });
+
+    let err = parser.critical_auto_msg().parse_str("a").unwrap_err();
+
+    assert_eq!(
+        err.critical_message().as_deref(),
+        Some("expected 3 arguments, found 5")
+    );
+}
+
+#[test]
+fn labelled_context() {
+    let args = char('(')
+        .then(char('a').separated_by(char(',')))
+        .then(char(')'))
+        .labelled("argument list");
+
+    let parser = just("fn f").then(args).labelled("function declaration");
+
+    let err = parser.parse_str("fn f(a,a;").err().unwrap();
+
+    let labels = err
+        .context()
+        .iter()
+        .map(|frame| (frame.label.as_ref(), frame.at.offset()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        labels,
+        vec![("argument list", 4), ("function declaration", 0)]
+    );
+
+    assert_eq!(
+        err.to_string(),
+        "while parsing function declaration (offset 0), while parsing argument list (offset 4): expected one of ',', ')', found ';'"
+    );
+
+    let err = parser
+        .atomic_err("invalid function")
+        .parse_str("fn f(a;")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.atomic_error(), Some("invalid function"));
+    assert_eq!(err.context().len(), 2);
+}
+
+#[test]
+fn secondary_labels_and_footers() {
+    let parser = char('{')
+        .spanned()
+        .then(char('a').repeated())
+        .spanned()
+        .and_then(
+            |Span {
+                 data: (open, _),
+                 at,
+             }| {
+                let close = at.start.add(at.len);
+
+                Err::<(), _>(
+                    ParsingError::custom(close.range(0), "expected '}'")
+                        .with_secondary_label(open.at, "opening '{' here")
+                        .with_note("blocks must be closed")
+                        .with_help("add a '}'"),
+                )
+            },
+        );
+
+    let err = parser.parse_str("{aa").err().unwrap();
+
+    assert_eq!(err.inner().at().start.offset(), 3);
+    assert_eq!(err.secondary_labels().len(), 1);
+    assert_eq!(err.secondary_labels()[0].at.start.offset(), 0);
+    assert_eq!(
+        err.secondary_labels()[0].message.format(),
+        "opening '{' here"
+    );
+    assert_eq!(err.notes(), ["blocks must be closed"]);
+    assert_eq!(err.help(), ["add a '}'"]);
+}
+
+#[test]
+fn structured_diagnostic() {
+    let parser = char('\n').then(
+        just("let ")
+            .then(char('a').critical("expected an identifier"))
+            .labelled("declaration"),
+    );
+
+    let source = "\nlet b";
+    let err = parser.parse_str(source).err().unwrap();
+
+    let diag = StructuredDiagnostic::from_parsing_error(source, &err).unwrap();
+
+    assert_eq!(diag.severity, Severity::Error);
+    assert_eq!(diag.message, "expected an identifier");
+    assert_eq!(diag.range, 5..6);
+    assert_eq!(diag.start, CoordsInString { line: 1, col: 4 });
+    assert_eq!(diag.end, CoordsInString { line: 1, col: 5 });
+    assert!(diag.critical);
+    assert!(!diag.atomic);
+    assert_eq!(diag.notes, ["while parsing declaration (line 2)"]);
+
+    let err = just("let").parse_str("").err().unwrap();
+    let diag = StructuredDiagnostic::from_parsing_error("", &err).unwrap();
+
+    assert_eq!(diag.message, "expected 'let', found end of input");
+    assert_eq!(diag.start, CoordsInString { line: 0, col: 0 });
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn structured_diagnostic_json() {
+    let source = "let a = 1";
+
+    let err = just("let a = 1")
+        .then(char(';').critical("expected ';'").with_suggestion(|err| {
+            Some(Suggestion::insert(
+                err.inner().at().start,
+                ";",
+                "add a semicolon",
+            ))
+        }))
+        .parse_str(source)
+        .err()
+        .unwrap();
+
+    let diag = StructuredDiagnostic::from_parsing_error(source, &err).unwrap();
+    let json = serde_json::to_value(&diag).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "severity": "Error",
+            "message": "unexpected end of input",
+            "code": null,
+            "file_id": "None",
+            "range": { "start": 9, "end": 9 },
+            "start": { "line": 0, "col": 9 },
+            "end": { "line": 0, "col": 9 },
+            "critical": true,
+            "atomic": false,
+            "labels": [],
+            "notes": [],
+            "help": [],
+            "suggestions": [{
+                "message": "add a semicolon",
+                "replacement": ";",
+                "file_id": "None",
+                "range": { "start": 9, "end": 9 },
+                "start": { "line": 0, "col": 9 },
+                "end": { "line": 0, "col": 9 },
+            }],
+        })
+    );
+
+    let deserialized: StructuredDiagnostic = serde_json::from_value(json).unwrap();
+
+    assert_eq!(deserialized, diag);
+}
+
+#[test]
+fn error_codes() {
+    let parser = char('a').then(char(';').critical_with_code("E0042", "missing semicolon"));
+
+    let err = parser.parse_str("ab").err().unwrap();
+
+    assert_eq!(err.code(), Some("E0042"));
+    assert_eq!(err.critical_message().as_deref(), Some("missing semicolon"));
+
+    // Nested critical errors are kept as they are
+    let err = parser
+        .critical_with_code("E0001", "invalid statement")
+        .parse_str("ab")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.code(), Some("E0042"));
+
+    let err = char('a')
+        .atomic_err_code("E0007", "expected an 'a'")
+        .parse_str("b")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.code(), Some("E0007"));
+    assert_eq!(err.atomic_error(), Some("expected an 'a'"));
+}
+
+#[test]
+fn error_payloads() {
+    let parser = filter(|c| c.is_ascii_digit())
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .and_then_or_error(|digits| digits.parse::<u8>());
+
+    assert_eq!(parser.parse_str("255").unwrap().data, 255);
+
+    let err = parser.parse_str("256").err().unwrap();
+
+    assert!(err.is_critical());
+    assert_eq!(
+        err.critical_message().as_deref(),
+        Some("number too large to fit in target type")
+    );
+    assert_eq!(
+        err.downcast_source::<ParseIntError>()
+            .map(ParseIntError::kind),
+        Some(&IntErrorKind::PosOverflow)
+    );
+    assert!(Error::source(&err).is_some());
+
+    #[derive(Debug)]
+    struct UnknownVariant(String);
+
+    impl fmt::Display for UnknownVariant {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "unknown variant `{}`", self.0)
+        }
+    }
+
+    impl Error for UnknownVariant {}
+
+    let parser = custom(|input| -> ParserResult<()> {
+        Err(ParsingError::from_source(
+            input.range(3),
+            UnknownVariant("red".to_owned()),
+        ))
+    });
+
+    let err = parser.parse_str("red").err().unwrap();
+
+    assert_eq!(err.message(), "unknown variant `red`");
+    assert_eq!(err.downcast_source::<UnknownVariant>().unwrap().0, "red");
+}
+
+#[test]
+fn found_input() {
+    let err = char(')').parse_str(";").err().unwrap();
+
+    assert_eq!(err.inner().found(), Some(&Found::Char(';')));
+    assert_eq!(err.message(), "expected char ')', found ';'");
+
+    let err = just("fn").parse_str("").err().unwrap();
+
+    assert_eq!(err.inner().found(), Some(&Found::EndOfInput));
+    assert_eq!(err.message(), "expected 'fn', found end of input");
+
+    let err = just("return").parse_str("retrun x").err().unwrap();
+
+    assert_eq!(err.inner().found(), Some(&Found::Token("retrun".into())));
+
+    let err = just("fn")
+        .then(char(' '))
+        .full()
+        .parse_str("fn x")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.inner().found(), Some(&Found::Char('x')));
+
+    let err = newline().parse_str("\t").err().unwrap();
+
+    assert_eq!(err.inner().found(), Some(&Found::Char('\t')));
+    assert_eq!(err.to_string(), "expected a newline, found '\\t'");
+}
+
+#[test]
+fn suggestions() {
+    let source = "let a = 1\nlet b = 2;";
+
+    let parser = just("let ")
+        .then(filter(|c| c.is_ascii_alphabetic()))
+        .then(just(" = "))
+        .then(filter(|c| c.is_ascii_digit()))
+        .then(char(';').critical("expected ';'").with_suggestion(|err| {
+            Some(Suggestion::insert(
+                err.inner().at().start,
+                ";",
+                "add a semicolon",
+            ))
+        }));
+
+    let err = parser.parse_str(source).err().unwrap();
+
+    assert_eq!(err.suggestions().len(), 1);
+    assert_eq!(
+        err.apply_suggestions(source).unwrap(),
+        "let a = 1;\nlet b = 2;"
+    );
+
+    let diag = StructuredDiagnostic::from_parsing_error(source, &err).unwrap();
+
+    assert_eq!(diag.suggestions[0].replacement, ";");
+    assert_eq!(diag.suggestions[0].range, 9..9);
+
+    let parser = filter(|c| c == '=')
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .validate_or_dynamic_critical(|op| {
+            if op == "==" {
+                Ok(())
+            } else {
+                Err("expected '=='".into())
+            }
+        })
+        .with_suggestion(|_, at| Some(Suggestion::replace(at, "==", "use '==' instead")));
+
+    let err = parser.parse_str("=").err().unwrap();
+
+    assert_eq!(err.critical_message().as_deref(), Some("expected '=='"));
+    assert_eq!(err.apply_suggestions("=").unwrap(), "==");
+
+    let start = InputLocation {
+        file_id: FileId::None,
+        offset: 0,
+    };
+
+    let insert = Suggestion::insert(start.add(4), "mut ", "make it mutable");
+    let rename = Suggestion::replace(start.add(4).range(1), "b", "rename it");
+    let remove = Suggestion::remove(start.add(5).range(4), "remove the value");
+
+    assert_eq!(
+        apply_suggestions("let a = 1;", [&rename, &insert, &remove]),
+        Ok("let mut b;".to_owned())
+    );
+
+    let overlapping = Suggestion::remove(start.add(4).range(3), "remove it");
+
+    assert_eq!(
+        apply_suggestions("let a = 1;", [&rename, &overlapping]),
+        Err(ApplySuggestionsErr::Overlapping)
+    );
+
+    assert_eq!(
+        apply_suggestions("let", [&rename]),
+        Err(ApplySuggestionsErr::OutOfBounds)
+    );
+}
+
+#[test]
+fn did_you_mean() {
+    let keyword = choice((just("return"), just("break"), just("continue")));
+
+    let source = "retrun x";
+    let err = keyword.parse_str(source).err().unwrap();
+
+    assert_eq!(
+        err.message(),
+        "expected one of 'return', 'break', 'continue', found 'retrun'"
+    );
+    assert_eq!(err.suggestions().len(), 1);
+    assert_eq!(
+        err.suggestions()[0].message.format(),
+        "did you mean 'return'?"
+    );
+    assert_eq!(err.apply_suggestions(source).unwrap(), "return x");
+
+    let err = keyword.parse_str("contineu").err().unwrap();
+
+    assert_eq!(err.suggestions()[0].replacement, "continue");
+
+    // Words too different from all candidates don't get a suggestion
+    let err = keyword.parse_str("value").err().unwrap();
+
+    assert!(err.suggestions().is_empty());
+
+    let err = just("let ")
+        .then(keyword)
+        .parse_str("let brek")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.suggestions()[0].replacement, "break");
+    assert_eq!(err.suggestions()[0].at.start.offset(), 4);
+
+    // Same for choices discarding the parsed value
+    let err = silent_choice((just("pub"), just("static")))
+        .parse_str("statci x")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.suggestions()[0].replacement, "static");
+    assert_eq!(err.apply_suggestions("statci x").unwrap(), "static x");
+
+    assert_eq!(
+        ParserExpectation::Str("while").closest_str("whlie"),
+        Some("while")
+    );
+}
+
+#[test]
+fn unclosed_delimiters() {
+    let group = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .surrounded_by(char('('), char(')'));
+
+    assert!(group.parse_str("(ab)").is_ok());
+
+    let err = group.parse_str("(ab").err().unwrap();
+
+    assert!(!err.is_critical());
+    assert_eq!(err.inner().at().start.offset(), 3);
+
+    let group = group.delimiter_aware(true);
+
+    let err = just("let x = ")
+        .then(group)
+        .parse_str("let x = (ab")
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err.critical_kind(),
+        Some(&ErrorKind::UnclosedDelimiter { opener: "(".into() })
+    );
+    assert_eq!(err.message(), "unclosed delimiter '('");
+    assert_eq!(err.inner().at().start.offset(), 11);
+    assert_eq!(err.secondary_labels()[0].at.start.offset(), 8);
+    assert_eq!(
+        err.secondary_labels()[0].message.format(),
+        "unclosed '(' opened here"
+    );
+
+    let err = group.parse_str("(ab]").err().unwrap();
+
+    assert_eq!(
+        err.critical_kind(),
+        Some(&ErrorKind::MismatchedDelimiter {
+            opener: "(".into(),
+            closer: ']'
+        })
+    );
+    assert_eq!(err.message(), "mismatched closing delimiter ']'");
+    assert_eq!(err.inner().at().start.offset(), 3);
+    assert_eq!(err.secondary_labels()[0].at.start.offset(), 0);
+    assert_eq!(
+        err.secondary_labels()[0].message.format(),
+        "'(' opened here does not match ']'"
+    );
+
+    // Only closing delimiters of the configured pairs are mismatched
+    let err = group
+        .delimiter_pairs(&[('(', ')'), ('<', '>')])
+        .parse_str("(ab]")
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err.critical_kind(),
+        Some(&ErrorKind::UnclosedDelimiter { opener: "(".into() })
+    );
+
+    let block = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .surrounded_by(just("begin "), just(" end"))
+        .delimiter_aware(true);
+
+    let err = block.parse_str("begin ab}").err().unwrap();
+
+    assert_eq!(
+        err.critical_kind(),
+        Some(&ErrorKind::UnclosedDelimiter {
+            opener: "begin ".into()
+        })
+    );
+    assert_eq!(
+        err.secondary_labels()[0].message.format(),
+        "unclosed 'begin ' opened here"
+    );
+}
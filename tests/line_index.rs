@@ -0,0 +1,133 @@
+use parsy::{ColumnUnit, CoordsInString, FileId, InputLocation, LineIndex, LspPosition, LspRange};
+
+#[test]
+fn offsets_to_coords() {
+    let input = "let a = 1;\r\nlet b = 2;\n\nend";
+    let index = LineIndex::new(input);
+
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.len(), input.len());
+    assert_eq!(index.line_range(0), Some(0..10));
+    assert_eq!(index.line_range(1), Some(12..22));
+    assert_eq!(index.line_range(2), Some(23..23));
+    assert_eq!(index.line_range(4), None);
+
+    assert_eq!(index.coords(4), Ok(CoordsInString { line: 0, col: 4 }));
+    assert_eq!(index.coords(16), Ok(CoordsInString { line: 1, col: 4 }));
+    assert_eq!(index.coords(23), Ok(CoordsInString { line: 2, col: 0 }));
+    assert_eq!(index.coords(27), Ok(CoordsInString { line: 3, col: 3 }));
+    assert!(index.coords(28).is_err());
+
+    // Line breaks are at the end of their line, whether they are CRLF or not
+    assert_eq!(index.coords(10), Ok(CoordsInString { line: 0, col: 10 }));
+    assert_eq!(index.coords(11), Ok(CoordsInString { line: 0, col: 10 }));
+    assert_eq!(index.coords(22), Ok(CoordsInString { line: 1, col: 10 }));
+
+    assert_eq!(index.offset(CoordsInString { line: 1, col: 4 }), Ok(16));
+    assert_eq!(index.offset(CoordsInString { line: 3, col: 3 }), Ok(27));
+    assert!(index.offset(CoordsInString { line: 0, col: 11 }).is_err());
+    assert!(index.offset(CoordsInString { line: 4, col: 0 }).is_err());
+
+    for offset in 0..input.len() {
+        let location = InputLocation {
+            file_id: FileId::None,
+            offset,
+        };
+
+        assert_eq!(
+            location.compute_coords_in(input).ok(),
+            location.compute_coords_with(&index).ok()
+        );
+    }
+
+    // Only the index accepts the end of the input
+    let end = InputLocation {
+        file_id: FileId::None,
+        offset: input.len(),
+    };
+
+    assert!(end.compute_coords_in(input).is_err());
+    assert_eq!(
+        end.compute_coords_with(&index),
+        Ok(CoordsInString { line: 3, col: 3 })
+    );
+
+    let empty = LineIndex::new("");
+
+    assert!(empty.is_empty());
+    assert_eq!(empty.line_count(), 1);
+    assert_eq!(empty.coords(0), Ok(CoordsInString { line: 0, col: 0 }));
+}
+
+#[test]
+fn column_units() {
+    // 'é' is 2 bytes and 1 UTF-16 code unit, '𝄞' is 4 bytes and 2 UTF-16 code units
+    let input = "name = \"é𝄞\"; x\r\n𝄞 = 1";
+    let index = LineIndex::new(input);
+
+    let x = input.find('x').unwrap();
+
+    assert_eq!(
+        index.coords_in_unit(input, x, ColumnUnit::Bytes),
+        Ok(CoordsInString { line: 0, col: 17 })
+    );
+    assert_eq!(
+        index.coords_in_unit(input, x, ColumnUnit::Chars),
+        Ok(CoordsInString { line: 0, col: 13 })
+    );
+    assert_eq!(
+        index.coords_in_unit(input, x, ColumnUnit::Utf16),
+        Ok(CoordsInString { line: 0, col: 14 })
+    );
+
+    // Offsets inside a character cannot be converted
+    assert!(index.coords_in_unit(input, 9, ColumnUnit::Chars).is_err());
+
+    for unit in [ColumnUnit::Bytes, ColumnUnit::Chars, ColumnUnit::Utf16] {
+        for (offset, _) in input.char_indices().filter(|(_, c)| *c != '\n') {
+            let coords = index.coords_in_unit(input, offset, unit).unwrap();
+
+            if offset != input.find('\r').unwrap() {
+                assert_eq!(index.offset_in_unit(input, coords, unit), Ok(offset));
+            }
+        }
+    }
+
+    // Columns inside a surrogate pair cannot be converted
+    assert!(
+        index
+            .offset_in_unit(input, CoordsInString { line: 1, col: 1 }, ColumnUnit::Utf16)
+            .is_err()
+    );
+
+    let at = InputLocation {
+        file_id: FileId::None,
+        offset: input.rfind('=').unwrap(),
+    }
+    .range(3);
+
+    assert_eq!(
+        at.to_lsp_range(input, &index),
+        Ok(LspRange {
+            start: LspPosition {
+                line: 1,
+                character: 3
+            },
+            end: LspPosition {
+                line: 1,
+                character: 6
+            },
+        })
+    );
+
+    assert_eq!(
+        index.offset_of_lsp_position(
+            input,
+            LspPosition {
+                line: 1,
+                character: 3
+            }
+        ),
+        Ok(at.start.offset)
+    );
+}
@@ -0,0 +1,153 @@
+use parsy::{
+    ErrorKind, Found, MessageFormatter, ParserConstUtils, ParserExpectation, ParserNonConstUtils,
+    parsers::helpers::{char, choice, digit, filter, just},
+};
+
+struct French;
+
+impl MessageFormatter for French {
+    fn kind(&self, kind: &ErrorKind) -> String {
+        match kind {
+            ErrorKind::Digit { .. } => "chiffre attendu".to_owned(),
+            ErrorKind::NotEnoughRepetitions { min, found } => {
+                format!("au moins {min} répétitions attendues, {found} trouvées")
+            }
+            ErrorKind::UnexpectedEndOfInput => "fin inattendue".to_owned(),
+            kind => kind.to_string(),
+        }
+    }
+
+    fn expectation(&self, expected: &ParserExpectation) -> String {
+        match expected {
+            ParserExpectation::Char(c) => format!("caractère '{c}' attendu"),
+            ParserExpectation::Kind(kind) => self.kind(kind),
+            expected => expected.to_string(),
+        }
+    }
+
+    fn found(&self, found: &Found) -> String {
+        match found {
+            Found::EndOfInput => "fin de l'entrée".to_owned(),
+            found => found.to_string(),
+        }
+    }
+
+    fn expected_found(&self, expected: &str, found: &str) -> String {
+        format!("{expected}, mais {found} trouvé")
+    }
+
+    fn did_you_mean(&self, candidate: &str) -> String {
+        format!("vouliez-vous dire '{candidate}' ?")
+    }
+
+    fn unclosed_delimiter_label(&self, opener: &str) -> String {
+        format!("'{opener}' ouvert ici n'est jamais fermé")
+    }
+}
+
+#[test]
+fn custom_formatter() {
+    let err = digit(10).parse_str("a").err().unwrap();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::Digit { radix: 10 })
+    );
+    assert_eq!(err.message(), "expected a digit, found 'a'");
+    assert_eq!(
+        err.message_with(&French),
+        "chiffre attendu, mais 'a' trouvé"
+    );
+
+    assert_eq!(
+        French.kind(&ErrorKind::NotEnoughRepetitions { min: 3, found: 2 }),
+        "au moins 3 répétitions attendues, 2 trouvées"
+    );
+
+    let err = just("a")
+        .then(char('b').critical_auto_msg())
+        .parse_str("a")
+        .err()
+        .unwrap();
+
+    assert_eq!(err.critical_kind(), Some(&ErrorKind::UnexpectedEndOfInput));
+    assert_eq!(
+        err.critical_message().as_deref(),
+        Some("unexpected end of input")
+    );
+    assert_eq!(err.message_with(&French), "fin inattendue");
+
+    // The default formatter is used when none is provided
+    let err = char('b').parse_str("").err().unwrap();
+
+    assert_eq!(err.to_string(), "expected char 'b', found end of input");
+    assert_eq!(
+        err.message_with(&French),
+        "caractère 'b' attendu, mais fin de l'entrée trouvé"
+    );
+}
+
+#[test]
+fn messages_are_formatted_when_displayed() {
+    let err = just("a")
+        .then(char('b').critical_auto_msg())
+        .parse_str("ac")
+        .err()
+        .unwrap();
+
+    assert!(err.is_critical());
+    assert_eq!(
+        err.message_with(&French),
+        "caractère 'b' attendu, mais 'c' trouvé"
+    );
+
+    let err = choice((just("return"), just("break")))
+        .parse_str("retrun")
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err.suggestions()[0].message.format_with(&French),
+        "vouliez-vous dire 'return' ?"
+    );
+
+    let err = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .surrounded_by(char('('), char(')'))
+        .delimiter_aware(true)
+        .parse_str("(ab")
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        err.secondary_labels()[0].message.format_with(&French),
+        "'(' ouvert ici n'est jamais fermé"
+    );
+}
+
+#[cfg(feature = "error-reporting")]
+#[test]
+fn report_formatter() {
+    use parsy::{ErrorReport, ReportOptions};
+
+    let source = "(ab";
+
+    let err = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .surrounded_by(char('('), char(')'))
+        .delimiter_aware(true)
+        .parse_str(source)
+        .err()
+        .unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions {
+            formatter: &French,
+            ..ReportOptions::plain()
+        });
+
+    let report = report.to_string();
+
+    assert!(report.contains("'(' ouvert ici n'est jamais fermé"));
+    assert!(!report.contains("opened here"));
+}
@@ -0,0 +1,139 @@
+use parsy::{
+    FileId, Parser, ParserConstUtils, ParserInput, ParserNonConstUtils, ParsingSession, UserState,
+    parsers::helpers::{char, filter, just, skip_until, whitespaces},
+};
+
+#[test]
+fn recover_statements() {
+    let ident = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string();
+
+    let statement = just("let ")
+        .ignore_then(ident.critical("expected an identifier"))
+        .then_ignore(char(';').critical("expected a semicolon"))
+        .recover_with(skip_until(char(';')))
+        .padded_by(whitespaces());
+
+    let parser = statement.repeated_into_vec().full();
+
+    let (parsed, errors) = parser.parse_str_recovering("let a; let 1; let b; let c");
+
+    assert_eq!(parsed, None);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].critical_message().as_deref(),
+        Some("expected an identifier")
+    );
+    assert_eq!(errors[0].inner().at().start.offset(), 11);
+    assert_eq!(
+        errors[1].critical_message().as_deref(),
+        Some("unexpected end of input")
+    );
+
+    let (parsed, errors) = parser.parse_str_recovering("let a; let 1; let b;");
+
+    assert_eq!(
+        parsed,
+        Some(vec!["a".to_owned(), String::new(), "b".to_owned()])
+    );
+    assert_eq!(errors.len(), 1);
+
+    let block = char('{')
+        .then(char('}').critical("expected a closing brace"))
+        .to(true)
+        .recover_nested('{', '}');
+
+    let (parsed, errors) = block
+        .repeated_into_vec()
+        .full()
+        .parse_str_recovering("{}{{}}{}");
+
+    assert_eq!(parsed, Some(vec![true, false, true]));
+    assert_eq!(errors.len(), 1);
+
+    let digit = filter(|c| c.is_ascii_digit())
+        .critical("expected a digit")
+        .recover_with_fallback('0');
+
+    let (parsed, errors) = digit
+        .repeated_into_vec()
+        .exactly(3)
+        .collect_string()
+        .full()
+        .parse_str_recovering("1a2");
+
+    assert_eq!(parsed.as_deref(), Some("1a2"));
+    assert_eq!(errors.len(), 1);
+
+    // Errors are only recovered from when explicitly requested
+    assert!(digit.parse_str("a").is_err());
+}
+
+#[test]
+fn recovery_makes_progress() {
+    // Recovering from an error at the end of the input would consume nothing
+    let (parsed, errors) = char('a')
+        .critical("expected a")
+        .recover_with_fallback('?')
+        .repeated_into_vec()
+        .parse_str_recovering("b");
+
+    assert_eq!(parsed, None);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].critical_message().as_deref(), Some("expected a"));
+    assert_eq!(
+        errors[1].critical_message().as_deref(),
+        Some("unexpected end of input")
+    );
+
+    // Repetitions stop when they don't consume anything
+    let parsed = char('a').or_not().repeated_into_vec().parse_str("b");
+
+    assert_eq!(parsed.unwrap().data, [None]);
+
+    let parsed = char('a')
+        .or_not()
+        .separated_by_into_vec(char(',').or_not())
+        .at_least(1)
+        .parse_str(",a,b");
+
+    assert_eq!(parsed.unwrap().data, [None, Some('a'), None, None]);
+}
+
+#[test]
+fn recovery_discards_failed_updates() {
+    struct Count(usize);
+
+    impl UserState for Count {
+        type Snapshot = usize;
+
+        fn snapshot(&self) -> usize {
+            self.0
+        }
+
+        fn restore(&mut self, snapshot: usize) {
+            self.0 = snapshot;
+        }
+    }
+
+    let parser = char('a')
+        .warn_if(|_| true, "counted")
+        .update_state(|_, count: &mut Count| count.0 += 1)
+        .then(char('b').critical("expected b"))
+        .recover_with_fallback(('?', '?'));
+
+    let session = ParsingSession::new().with_recovery().with_state(Count(0));
+
+    let parsed = parser
+        .parse(&mut ParserInput::new("ac", FileId::None, &session))
+        .unwrap();
+
+    assert_eq!(parsed.data, ('?', '?'));
+    assert_eq!(session.take_recovered_errors().len(), 1);
+
+    // The diagnostic and the state update of the parser that failed are discarded
+    assert!(session.take_diagnostics().is_empty());
+    assert_eq!(session.into_state().0, 0);
+}
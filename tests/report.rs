@@ -0,0 +1,178 @@
+#![cfg(feature = "error-reporting")]
+
+use parsy::{
+    ErrorReport, ParserConstUtils, ParserNonConstUtils, ReportOptions, Suggestion,
+    parsers::helpers::{char, just},
+};
+
+#[test]
+fn plain_report() {
+    let source = "let a = 1;\nlet b = 2\nlet c = 3;\n";
+
+    let parser = just("let ")
+        .then(char('a').or(char('b')).or(char('c')))
+        .then(just(" = "))
+        .then(char('1').or(char('2')).or(char('3')))
+        .then(char(';'))
+        .then(char('\n'))
+        .repeated()
+        .full();
+
+    let err = parser.parse_str(source).err().unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions {
+            context_lines: 1,
+            title: Some("Invalid statement".into()),
+            ..ReportOptions::plain()
+        });
+
+    assert_eq!(
+        report.to_string(),
+        [
+            "error: Invalid statement",
+            " --> input.txt:2:10",
+            "  |",
+            "1 | let a = 1;",
+            "2 | let b = 2",
+            "  |          ^ expected char ';', found '\\n'",
+            "3 | let c = 3;",
+            "  |",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn tab_expansion() {
+    let source = "\tlet a = 1;\n\t\tlet b = 2;";
+
+    let parser = char('\t')
+        .repeated()
+        .then(just("let "))
+        .then(char('a'))
+        .then(just(" = 1;\n"))
+        .repeated()
+        .full();
+
+    let err = parser.parse_str(source).err().unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions {
+            tab_width: Some(2),
+            ..ReportOptions::plain()
+        });
+
+    assert_eq!(
+        report.to_string(),
+        [
+            "error: Parsing failed",
+            " --> input.txt:2:7",
+            "  |",
+            "1 |   let a = 1;",
+            "2 |     let b = 2;",
+            "  |         ^ expected char 'a', found 'b'",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn error_code() {
+    let source = "a";
+
+    let err = char('b')
+        .critical_with_code("E0042", "expected a 'b'")
+        .parse_str(source)
+        .err()
+        .unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions::plain());
+
+    assert!(
+        report
+            .to_string()
+            .starts_with("error[E0042]: Parsing failed\n")
+    );
+}
+
+#[test]
+fn suggestion() {
+    let source = "let a = 1\nlet b = 2;\n";
+
+    let err = just("let a = 1")
+        .then(char(';').critical("expected ';'").with_suggestion(|err| {
+            Some(Suggestion::insert(
+                err.inner().at().start,
+                ";",
+                "add a semicolon",
+            ))
+        }))
+        .parse_str(source)
+        .err()
+        .unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions::plain());
+
+    assert_eq!(
+        report.to_string(),
+        [
+            "error: Parsing failed",
+            " --> input.txt:1:10",
+            "  |",
+            "1 | let a = 1",
+            "  |          ^ expected ';'",
+            "2 | let b = 2;",
+            "  |",
+            "help: add a semicolon",
+            "  |",
+            "1 | let a = 1;",
+            "  |          +",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn tab_expansion_in_suggestion() {
+    let source = "{\n\tlet a = 1\n}\n";
+
+    let err = just("{\n\tlet a = 1")
+        .then(char(';').critical("expected ';'").with_suggestion(|err| {
+            Some(Suggestion::insert(
+                err.inner().at().start,
+                ";",
+                "add a semicolon",
+            ))
+        }))
+        .parse_str(source)
+        .err()
+        .unwrap();
+
+    let report =
+        ErrorReport::parsing_error(source, "input.txt", &err).with_options(ReportOptions {
+            tab_width: Some(2),
+            ..ReportOptions::plain()
+        });
+
+    assert_eq!(
+        report.to_string(),
+        [
+            "error: Parsing failed",
+            " --> input.txt:2:11",
+            "  |",
+            "1 | {",
+            "2 |   let a = 1",
+            "  |            ^ expected ';'",
+            "3 | }",
+            "  |",
+            "help: add a semicolon",
+            "  |",
+            "2 |   let a = 1;",
+            "  |            +",
+        ]
+        .join("\n")
+    );
+}
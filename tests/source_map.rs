@@ -0,0 +1,152 @@
+use parsy::{
+    CoordsInString, FileId, ParserConstUtils, ParserNonConstUtils, SourceMap, SourceMapErr,
+    parsers::helpers::{char, filter, just},
+};
+
+#[test]
+fn resolve_locations() {
+    let mut source_map = SourceMap::new();
+
+    let main = source_map.add_file("main.cfg", "include \"lib.cfg\"\nname = main\n");
+    let lib = source_map.add_file("lib.cfg", "version = 1\n");
+
+    assert_ne!(main, lib);
+    assert_eq!(source_map.get(lib).unwrap().path(), "lib.cfg");
+    assert_eq!(source_map.files().count(), 2);
+
+    let parser = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .then_ignore(just(" = "))
+        .then(filter(|c| c.is_ascii_alphanumeric()).repeated().at_least(1))
+        .then_ignore(char('\n'));
+
+    let parsed = source_map.parse(lib, &parser).unwrap().unwrap();
+
+    assert_eq!(parsed.at.start.file_id, FileId::SourceFile(lib));
+    assert_eq!(source_map.extract(parsed.at).unwrap(), "version = 1\n");
+
+    let err = source_map.parse(main, &parser).unwrap().err().unwrap();
+
+    assert_eq!(err.inner().at().start.file_id, FileId::SourceFile(main));
+    assert_eq!(
+        source_map.coords(err.inner().at().start).unwrap(),
+        CoordsInString { line: 0, col: 7 }
+    );
+
+    let out_of_bounds = parsed.at.start.add(100);
+
+    assert_eq!(
+        source_map.coords(out_of_bounds),
+        Err(SourceMapErr::OutOfBounds)
+    );
+
+    assert_eq!(
+        source_map.extract(out_of_bounds.range(0)),
+        Err(SourceMapErr::OutOfBounds)
+    );
+
+    let unknown = just("version").parse_str("version").unwrap();
+
+    assert_eq!(
+        source_map.extract(unknown.at),
+        Err(SourceMapErr::UnknownFile)
+    );
+
+    assert_eq!(
+        SourceMap::new().parse(lib, &parser).err(),
+        Some(SourceMapErr::UnknownFile)
+    );
+}
+
+#[cfg(feature = "error-reporting")]
+#[test]
+fn multi_file_report() {
+    use parsy::{InputLocation, ParsingError, ReportOptions};
+
+    let mut source_map = SourceMap::new();
+
+    let lib = source_map.add_file("lib.cfg", "name = lib\n");
+    let main = source_map.add_file("main.cfg", "version = 1\nname = main\n");
+
+    let name = just("name = ").then(filter(|c| c.is_ascii_alphabetic()).repeated());
+
+    let first_definition = source_map.parse(lib, &name).unwrap().unwrap();
+
+    let redefinition = InputLocation {
+        file_id: FileId::SourceFile(main),
+        offset: 12,
+    };
+
+    let err = ParsingError::custom(redefinition.range(11), "name is already defined")
+        .with_secondary_label(first_definition.at, "first defined here");
+
+    let report = source_map
+        .report(&err)
+        .unwrap()
+        .with_options(ReportOptions::plain());
+
+    assert_eq!(
+        report.to_string(),
+        [
+            "error: Parsing failed",
+            " --> main.cfg:2:1",
+            "  |",
+            "1 | version = 1",
+            "2 | name = main",
+            "  | ^^^^^^^^^^^ name is already defined",
+            "  |",
+            " ::: lib.cfg:1:1",
+            "  |",
+            "1 | name = lib",
+            "  | ---------- first defined here",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn multi_file_structured_diagnostic() {
+    use parsy::{InputLocation, ParsingError, StructuredDiagnostic, Suggestion};
+
+    let mut source_map = SourceMap::new();
+
+    let lib = source_map.add_file("lib.cfg", "name = lib\n");
+    let main = source_map.add_file("main.cfg", "version = 1\nname = main\n");
+
+    let name = just("name = ").then(filter(|c| c.is_ascii_alphabetic()).repeated());
+
+    let first_definition = source_map.parse(lib, &name).unwrap().unwrap();
+
+    let redefinition = InputLocation {
+        file_id: FileId::SourceFile(main),
+        offset: 12,
+    };
+
+    let err = ParsingError::custom(redefinition.range(11), "name is already defined")
+        .with_secondary_label(first_definition.at, "first defined here")
+        .with_suggestion(Suggestion::remove(first_definition.at, "remove it"));
+
+    let diag = StructuredDiagnostic::from_source_map(&source_map, &err).unwrap();
+
+    assert_eq!(diag.file_id, FileId::SourceFile(main));
+    assert_eq!(diag.start, CoordsInString { line: 1, col: 0 });
+    assert_eq!(diag.labels[0].file_id, FileId::SourceFile(lib));
+    assert_eq!(diag.labels[0].range, 0..10);
+    assert_eq!(diag.labels[0].end, CoordsInString { line: 0, col: 10 });
+    assert_eq!(diag.suggestions[0].file_id, FileId::SourceFile(lib));
+
+    // Without the source map, locations in other files cannot be resolved
+    let main_file = source_map.get(main).unwrap();
+    let diag = StructuredDiagnostic::from_parsing_error_with(main_file.line_index(), &err).unwrap();
+
+    assert_eq!(diag.start, CoordsInString { line: 1, col: 0 });
+    assert!(diag.labels.is_empty());
+    assert!(diag.suggestions.is_empty());
+
+    assert_eq!(
+        StructuredDiagnostic::from_source_map(&SourceMap::new(), &err),
+        Err(SourceMapErr::UnknownFile)
+    );
+}
@@ -0,0 +1,153 @@
+use std::cell::Cell;
+
+use parsy::{
+    ParserConstUtils, ParserNonConstUtils, UserState,
+    parsers::helpers::{char, choice, filter, just, lookahead, whitespaces},
+};
+
+/// Names declared as types, like C's typedefs
+#[derive(Debug, Default)]
+struct TypeNames {
+    names: Vec<String>,
+}
+
+impl UserState for TypeNames {
+    type Snapshot = usize;
+
+    fn snapshot(&self) -> usize {
+        self.names.len()
+    }
+
+    fn restore(&mut self, len: usize) {
+        self.names.truncate(len);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Stmt {
+    Typedef(String),
+    Declaration(String, String),
+    Multiplication(String, String),
+}
+
+#[test]
+fn typedef_problem() {
+    let ident = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string();
+
+    let type_name = ident.validate_with_state(|name, types: &TypeNames| types.names.contains(name));
+
+    let typedef = just("typedef ")
+        .ignore_then(ident)
+        .update_state(|name, types: &mut TypeNames| types.names.push(name.clone()))
+        .map(Stmt::Typedef);
+
+    // 'a * b' is a declaration if 'a' is a type, a multiplication otherwise
+    let declaration = type_name
+        .then_ignore(just(" * "))
+        .then(ident)
+        .map(|(ty, name)| Stmt::Declaration(ty, name));
+
+    let multiplication = ident
+        .then_ignore(just(" * "))
+        .then(ident)
+        .map(|(a, b)| Stmt::Multiplication(a, b));
+
+    let parser = choice((typedef, declaration, multiplication))
+        .then_ignore(char(';'))
+        .padded_by(whitespaces())
+        .repeated_into_vec()
+        .full();
+
+    let (parsed, types) =
+        parser.parse_str_with_state("a * b; typedef a; a * b;", TypeNames::default());
+
+    assert_eq!(
+        parsed.unwrap().data,
+        [
+            Stmt::Multiplication("a".into(), "b".into()),
+            Stmt::Typedef("a".into()),
+            Stmt::Declaration("a".into(), "b".into()),
+        ]
+    );
+
+    assert_eq!(types.names, ["a"]);
+
+    // Updates from failed branches and lookaheads are undone
+    let parser = choice((
+        just("typedef ")
+            .ignore_then(ident)
+            .update_state(|name, types: &mut TypeNames| types.names.push(name.clone()))
+            .then_ignore(char('!')),
+        just("typedef ").ignore_then(ident).then_ignore(char('?')),
+    ))
+    .then_ignore(lookahead(ident.update_state(
+        |name, types: &mut TypeNames| types.names.push(name.clone()),
+    )))
+    .then_ignore(ident)
+    .full();
+
+    let types = TypeNames {
+        names: vec!["int".into()],
+    };
+
+    let (parsed, types) = parser.parse_str_with_state("typedef b?c", types);
+
+    assert_eq!(parsed.unwrap().data, "b");
+    assert_eq!(types.names, ["int"]);
+}
+
+/// Counts the snapshots taken from it
+#[derive(Debug, Default)]
+struct SnapshotCounter {
+    separators: usize,
+    snapshots: Cell<usize>,
+}
+
+impl UserState for SnapshotCounter {
+    type Snapshot = usize;
+
+    fn snapshot(&self) -> usize {
+        self.snapshots.set(self.snapshots.get() + 1);
+        self.separators
+    }
+
+    fn restore(&mut self, separators: usize) {
+        self.separators = separators;
+    }
+}
+
+#[test]
+fn snapshots_at_backtracking_points() {
+    // Only the entry point can backtrack
+    let (parsed, state) = just("a")
+        .then(char('b'))
+        .then(char('c'))
+        .parse_str_with_state("abc", SnapshotCounter::default());
+
+    assert!(parsed.is_ok());
+    assert_eq!(state.snapshots.get(), 1);
+
+    // One more for the optional parser
+    let (parsed, state) = just("a")
+        .then(char('b').or_not())
+        .then(char('c'))
+        .parse_str_with_state("ac", SnapshotCounter::default());
+
+    assert!(parsed.is_ok());
+    assert_eq!(state.snapshots.get(), 2);
+
+    // A trailing separator is discarded along with what it did
+    let (parsed, state) = char('a')
+        .separated_by(
+            char(',').update_state(|_, state: &mut SnapshotCounter| state.separators += 1),
+        )
+        .then_ignore(char(','))
+        .full()
+        .parse_str_with_state("a,a,", SnapshotCounter::default());
+
+    assert!(parsed.is_ok());
+    assert_eq!(state.separators, 1);
+}
@@ -0,0 +1,124 @@
+use std::io::{self, Read};
+
+use parsy::{
+    ErrorKind, ParserConstUtils, ParserExpectation, ParserNonConstUtils,
+    parsers::helpers::{char, empty, filter, newline},
+};
+
+/// Reader returning at most a few bytes at a time, to split records between reads
+struct Trickle<'a> {
+    content: &'a [u8],
+    chunk: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.chunk.min(buf.len()).min(self.content.len());
+
+        buf[..len].copy_from_slice(&self.content[..len]);
+        self.content = &self.content[len..];
+
+        Ok(len)
+    }
+}
+
+/// Reader failing after its content has been read
+struct Failing<'a>(&'a [u8]);
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::Error::other("connection lost"));
+        }
+
+        let len = buf.len().min(self.0.len());
+
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+
+        Ok(len)
+    }
+}
+
+#[test]
+fn records() {
+    let word = filter(|c| c.is_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string();
+
+    let record = word
+        .then_ignore(char('='))
+        .then(word)
+        .then_ignore(newline());
+
+    let content = "name=café\nnationalité=française\nkey=value\n";
+
+    for chunk in [1, 2, 3, 7, 1024] {
+        let records = record
+            .parse_stream(Trickle {
+                content: content.as_bytes(),
+                chunk,
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            records.iter().map(|span| &span.data).collect::<Vec<_>>(),
+            [
+                &("name".to_owned(), "café".to_owned()),
+                &("nationalité".to_owned(), "française".to_owned()),
+                &("key".to_owned(), "value".to_owned()),
+            ]
+        );
+
+        // Locations are absolute offsets in the stream
+        assert_eq!(records[1].at.start.offset(), 11);
+        assert_eq!(records[2].at.start.offset(), 35);
+    }
+
+    let mut records = record.parse_stream(Trickle {
+        content: b"a=b\nc=d\ne=f=g\nh=i\n",
+        chunk: 2,
+    });
+
+    assert!(records.next().unwrap().is_ok());
+    assert!(records.next().unwrap().is_ok());
+
+    let err = records.next().unwrap().unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 11);
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn stream_errors() {
+    let line = filter(|c| c != '\n')
+        .repeated()
+        .collect_string()
+        .then_ignore(newline());
+
+    let mut records = line.parse_stream(Failing(b"first\nsecond"));
+
+    assert_eq!(records.next().unwrap().unwrap().data, "first");
+
+    let err = records.next().unwrap().unwrap_err();
+
+    assert_eq!(err.inner().at().start.offset(), 6);
+    assert_eq!(err.source_error().unwrap().to_string(), "connection lost");
+
+    let empty = empty();
+
+    let mut records = empty.parse_stream(Trickle {
+        content: b"abc",
+        chunk: 1,
+    });
+
+    let err = records.next().unwrap().unwrap_err();
+
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::Kind(ErrorKind::EmptyRecord)
+    );
+    assert!(records.next().is_none());
+}
@@ -0,0 +1,126 @@
+use parsy::{
+    ErrorKind, Found, ParserConstUtils, ParserExpectation, ParserNonConstUtils, Span,
+    parsers::helpers::{char, choice, filter, select, token, whitespaces},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Comma,
+    Ident(String),
+    Number(u64),
+}
+
+fn lex(source: &str) -> Vec<Span<Token>> {
+    let ident = filter(|c| c.is_ascii_alphabetic())
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .map(Token::Ident);
+
+    let number = filter(|c| c.is_ascii_digit())
+        .repeated()
+        .at_least(1)
+        .collect_string()
+        .map(|num| Token::Number(num.parse().unwrap()));
+
+    let token = choice((
+        char('(').map(|_| Token::LParen),
+        char(')').map(|_| Token::RParen),
+        char(',').map(|_| Token::Comma),
+        ident,
+        number,
+    ));
+
+    token
+        .spanned()
+        .padded_by(whitespaces().no_newline())
+        .repeated_into_vec()
+        .full()
+        .parse_str(source)
+        .unwrap()
+        .data
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Ident(String),
+    Number(u64),
+}
+
+#[test]
+fn parse_tokens() {
+    let value = select(|token: &Token| match token {
+        Token::Ident(ident) => Some(Value::Ident(ident.clone())),
+        Token::Number(num) => Some(Value::Number(*num)),
+        _ => None,
+    });
+
+    let parser = value
+        .separated_by_into_vec(token(Token::Comma))
+        .surrounded_by(token(Token::LParen), token(Token::RParen))
+        .full();
+
+    let parsed = parser.parse_tokens(&lex(" (a, 12, b) ")).unwrap();
+
+    assert_eq!(
+        parsed.data,
+        vec![
+            Value::Ident("a".into()),
+            Value::Number(12),
+            Value::Ident("b".into())
+        ]
+    );
+
+    // Locations are converted back to the source
+    assert_eq!((parsed.at.start.offset(), parsed.at.len), (1, 10));
+
+    let err = parser.parse_tokens(&lex("(a, 12 b)")).unwrap_err();
+
+    assert_eq!(
+        (err.inner().at().start.offset(), err.inner().at().len),
+        (7, 1)
+    );
+    assert_eq!(
+        err.inner().found(),
+        Some(&Found::Token("Ident(\"b\")".into()))
+    );
+    assert_eq!(
+        err.inner().expected(),
+        &ParserExpectation::OneOf(vec![
+            ParserExpectation::Kind(ErrorKind::Token {
+                token: "Comma".into()
+            }),
+            ParserExpectation::Kind(ErrorKind::Token {
+                token: "RParen".into()
+            })
+        ])
+    );
+
+    let err = parser.parse_tokens(&lex("(a, ")).unwrap_err();
+
+    assert_eq!(
+        (err.inner().at().start.offset(), err.inner().at().len),
+        (3, 0)
+    );
+    assert_eq!(err.inner().found(), Some(&Found::EndOfInput));
+}
+
+#[test]
+fn unclosed_token_delimiter() {
+    let parser = token(Token::Comma)
+        .repeated_into_vec()
+        .surrounded_by(token(Token::LParen), token(Token::RParen))
+        .delimiter_aware(true)
+        .full();
+
+    let err = parser.parse_tokens(&lex("(, ,")).unwrap_err();
+
+    assert_eq!(
+        err.critical_kind(),
+        Some(&ErrorKind::UnclosedDelimiter {
+            opener: "LParen".into()
+        })
+    );
+}